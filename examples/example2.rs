@@ -128,6 +128,310 @@ fn example(c: Common)
 	.set_y_log(Some(3.0));
 
 	c.show(&mut fg, "fg2.7.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Steps and stems", &[])
+	.fill_between_steps(x, y1.map(|&y| y as f32 * 0.8), y1.map(|&y| y as f32 * 1.2), &[Color("#aaaaff"), Caption("Band")])
+	.steps(x, y1, &[LineWidth(2.0), Color("blue"), Caption("Steps")])
+	.histeps(x, y1, &[Color("black"), LineStyle(Dash), Caption("Histeps")])
+	.stem(x3, y3, &[PointSymbol('O'), Color("red"), Caption("Stem")]);
+
+	c.show(&mut fg, "fg2.8.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Scatter", &[])
+	.set_cb_label("Color", &[])
+	.scatter(x3, y3, x3, x3.map(|&v| 1.0 + 0.5 * v.abs() as f32), &[PointSymbol('O'), Caption("Palette")])
+	.scatter_rgb(x, y1, [(255u8, 0u8, 0u8), (0, 255, 0), (0, 0, 255), (255, 0, 255), (0, 255, 255)].iter().cloned(), repeat(2.0f32), &[PointSymbol('S'), Caption("RGB")]);
+
+	c.show(&mut fg, "fg2.9.gnuplot");
+
+	let theta: Vec<f64> = (0..361).map(|t| t as f64).collect();
+	let r: Vec<f64> = theta.iter().map(|&t| (3.0 * t.to_radians()).cos().abs()).collect();
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Polar", &[])
+	.set_polar(ThetaTop, true)
+	.set_polar_grid(Fix(30.0), &[LineStyle(Dot)])
+	.set_aspect_ratio(Fix(1.0))
+	.set_border(false, &[], &[])
+	.set_x_ticks(None, &[], &[])
+	.set_y_ticks(None, &[], &[])
+	.set_r_range(Fix(0.0), Fix(1.0))
+	.set_r_ticks(Some((Fix(0.25), 0)), &[], &[])
+	.set_theta_ticks(Some((Fix(30.0), 0)), &[], &[])
+	.lines(theta.iter(), r.iter(), &[LineWidth(2.0), Color("blue")]);
+
+	c.show(&mut fg, "fg2.10.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Parametric", &[])
+	.set_aspect_ratio(Fix(1.0))
+	.parametric((0.0, 2.0 * std::f64::consts::PI), 20, |t| ((3.0 * t).sin(), (2.0 * t).sin()), &[LineWidth(2.0), Color("blue"), Caption("Lissajous")]);
+
+	c.show(&mut fg, "fg2.11.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Functions", &[])
+	.set_x_range(Fix(-5.0), Fix(5.0))
+	.set_y_range(Fix(-5.0), Fix(5.0))
+	.points(x3, y3, &[PointSymbol('O'), Caption("Data")])
+	.function(|x| x * x * x, (Auto, Auto), 50, &[Color("blue"), Caption("x^3")])
+	.function(|x| 1.0 / x, (Auto, Auto), 50, &[Color("red"), Caption("1 / x")])
+	.function(|x| x.ln(), (Fix(0.0), Auto), 50, &[Color("black"), LineStyle(Dash), Caption("ln(x)")]);
+
+	c.show(&mut fg, "fg2.12.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Expressions", &[])
+	.set_x_range(Fix(0.0), Fix(20.0))
+	.set_samples(500, 500)
+	.expression("sin(x) * exp(-x / 5)", &[Color("blue"), Caption("sin(x) exp(-x / 5)")])
+	.expression("exp(-x / 5)", &[Color("black"), LineStyle(Dash), Caption("Envelope")]);
+
+	c.show(&mut fg, "fg2.13.gnuplot");
+
+	let categories = ["Q1", "Q2", "Q3", "Q4"];
+	let series: [(&str, &[f64]); 3] = [("North", &[3.0, 4.0, 2.5, 5.0]), ("South", &[2.0, 1.5, 3.0, 2.0]), ("West", &[1.0, 2.0, 2.0, 1.5])];
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_pos_grid(1, 3, 0)
+	.set_title("Grouped", &[])
+	.bar_chart(&categories, &series, Grouped, false, &[]);
+
+	fg.axes2d()
+	.set_pos_grid(1, 3, 1)
+	.set_title("Stacked", &[])
+	.bar_chart(&categories, &series, Stacked, false, &[BorderColor("black")]);
+
+	fg.axes2d()
+	.set_pos_grid(1, 3, 2)
+	.set_title("Percent, horizontal", &[])
+	.bar_chart(&categories, &series, PercentStacked, true, &[FillAlpha(0.7)]);
+
+	c.show(&mut fg, "fg2.14.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_pos_grid(1, 2, 0)
+	.set_title("Pie", &[])
+	.pie([5.0, 3.0, 2.0, 1.0].iter(), &["Rust", "C++", "Python", "Other"], &[PercentLabels, Explode(0, 0.1)], &[BorderColor("black")]);
+
+	fg.axes2d()
+	.set_pos_grid(1, 2, 1)
+	.set_title("Donut", &[])
+	.pie([5.0, 3.0, 2.0, 1.0].iter(), &["Rust", "C++", "Python", "Other"], &[HoleRadius(0.5), StartAngle(0.0)], &[]);
+
+	c.show(&mut fg, "fg2.15.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Circles and ellipses", &[])
+	.set_aspect_ratio(Fix(1.0))
+	.circles(x, y1, x.map(|&v| v as f32 * 0.3), &[Color("blue"), FillAlpha(0.3), BorderColor("black"), Caption("Bubbles")])
+	.circles_fixed_radius(x, y1, 0.1, &[Color("black"), Caption("Centers")])
+	.ellipses(Some(3.0).iter(), Some(15.0).iter(), Some(4.0).iter(), Some(6.0).iter(), Some(30.0).iter(), &[EllipseUnits(UnitsXY), Color("red"), FillAlpha(0.2), BorderColor("red"), Caption("Ellipse")]);
+
+	c.show(&mut fg, "fg2.16.gnuplot");
+
+	let samples = [1.2f64, 1.9, 2.1, 2.2, 2.8, 3.0, 3.1, 3.3, 4.0, 5.5];
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Smoothing", &[])
+	.points(x3, y3, &[PointSymbol('O'), Caption("Data")])
+	.lines(x3, y3, &[Smooth(CSplines), Color("blue"), Caption("csplines")])
+	.lines(x3, y3, &[Smooth(Bezier), Color("red"), Caption("bezier")]);
+
+	fg.axes2d()
+	.set_pos(0.6, 0.55)
+	.set_size(0.35, 0.35)
+	.set_title("Density", &[])
+	.lines(samples.iter(), repeat(1.0 / samples.len() as f64), &[Smooth(KDensity(Fix(0.5))), Color("black")])
+	.lines(samples.iter(), repeat(1.0), &[Smooth(CNormal), Color("gray")]);
+
+	c.show(&mut fg, "fg2.17.gnuplot");
+
+	// Fitting runs gnuplot to do the work, so skip it when not showing the figures
+	if !c.no_show
+	{
+		let xs = [0.0f64, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+		let ys = [10.1f64, 6.2, 3.6, 2.3, 1.3, 0.8, 0.5];
+
+		let mut fg = Figure::new();
+		c.set_term(&mut fg);
+
+		{
+			let ax = fg.axes2d();
+			ax.set_title("Fitting", &[])
+			.points(xs.iter(), ys.iter(), &[PointSymbol('O'), Caption("Data")]);
+			match ax.fit(xs.iter(), ys.iter(), "a * exp(-x / b)", &[("a", 10.0), ("b", 1.0)], Some(&[Color("red"), Caption("Fit")][..]))
+			{
+				Ok(res) => println!("a = {} +/- {}, b = {} +/- {}", res.params[0].1, res.errors[0], res.params[1].1, res.errors[1]),
+				Err(e) => println!("Fit failed: {}", e)
+			}
+			match ax.stats_xy(xs.iter(), ys.iter())
+			{
+				Ok(st) =>
+				{
+					ax.label(&format!("mean y = {:.2}, r = {:.2}", st.y.mean, st.correlation), Graph(0.5), Graph(0.9), &[]);
+				},
+				Err(e) => println!("Stats failed: {}", e)
+			}
+		}
+
+		c.show(&mut fg, "fg2.18.gnuplot");
+	}
+
+	let hours = [0.0f64, 3.0, 6.0, 9.0, 12.0, 15.0, 18.0, 21.0];
+	let temp = [12.0f64, 11.0, 13.5, 17.0, 21.0, 22.5, 19.0, 15.0];
+	let pressure = [1012.0f64, 1011.5, 1010.0, 1008.0, 1007.5, 1008.5, 1010.5, 1011.0];
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Secondary axes", &[])
+	.set_x_label("Hour", &[])
+	.set_y_label("Temperature (C)", &[])
+	.set_y_ticks(Some((Auto, 0)), &[Mirror(false)], &[])
+	.set_y2_label("Pressure (hPa)", &[])
+	.set_y2_ticks(Some((Auto, 0)), &[], &[])
+	.set_y2_range(Fix(1000.0), Fix(1020.0))
+	.set_x_ticks(Some((Fix(3.0), 2)), &[], &[])
+	.set_grid(&[XTickAxis, YTickAxis], true, false, &[Color("gray"), LineStyle(Solid)], &[Color("#dddddd"), LineStyle(Dot)])
+	.lines_points(hours.iter(), temp.iter(), &[Color("red"), Caption("Temperature")])
+	.lines_points(hours.iter(), pressure.iter(), &[Color("blue"), Axes(X1, Y2), Caption("Pressure")])
+	.label("Low", Axis(12.0), Second(1007.5), &[TextOffset(0.0, -1.0)]);
+
+	c.show(&mut fg, "fg2.19.gnuplot");
+
+	let freqs = [10.0f64, 100.0, 1e3, 1e4, 1e5, 1e6];
+	let gain = [0.0f64, -0.5, -3.0, -20.0, -40.0, -60.0];
+	let db_to_ratio = |db: f64| format!("{:.3}", 10.0f64.powf(db / 20.0));
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Tick formats", &[])
+	.set_x_log(Some(10.0))
+	.set_x_tick_format(SIPrefix(0))
+	.set_x_ticks(Some((Auto, 0)), &[], &[Rotate(-45.0), TextAlign(AlignLeft)])
+	.set_x_label("Frequency (Hz)", &[])
+	.set_y_range(Fix(-60.0), Fix(0.0))
+	.set_y_tick_format(Printf("%.0f dB"))
+	.set_y_ticks(Some((Auto, 0)), &[Mirror(false)], &[])
+	.set_y2_range(Fix(-60.0), Fix(0.0))
+	.set_y2_tick_formatter(db_to_ratio, &[], &[])
+	.lines(freqs.iter(), gain.iter(), &[Color("blue")]);
+
+	c.show(&mut fg, "fg2.20.gnuplot");
+
+	let minutes = [0.0f64, 20.0, 45.0, 70.0, 90.0, 130.0, 160.0];
+	let cost = [0.0f64, 120.0, 340.0, 610.0, 700.0, 1150.0, 1480.0];
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Tick formatters", &[])
+	.set_x_tick_formatter(|m| format!("{}h {:02}m", (m / 60.0).floor(), m % 60.0), &[], &[])
+	.set_y_tick_formatter(|v| format!("${:.0}", v), &[], &[])
+	.lines_points(minutes.iter(), cost.iter(), &[Color("dark-green")]);
+
+	c.show(&mut fg, "fg2.21.gnuplot");
+
+	let depth = [0.0f64, 10.0, 20.0, 50.0, 100.0, 200.0];
+	let water_temp = [18.0f64, 17.5, 15.0, 10.0, 6.0, 4.5];
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Reversed axis", &[])
+	.set_x_label("Temperature (C)", &[])
+	.set_y_label("Depth (m)", &[])
+	.set_range_options(YTickAxis, &[ReverseRange, NoExtend])
+	.set_offsets(GraphOffset(0.05), GraphOffset(0.05), AxisOffset(0.0), AxisOffset(0.0))
+	.lines_points(water_temp.iter(), depth.iter(), &[Color("blue")]);
+
+	c.show(&mut fg, "fg2.22.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_pos_grid(1, 2, 0)
+	.set_title("Mixed coordinates", &[])
+	.lines(x, y1, &[Color("blue")])
+	.arrow(Axis(3.0), Graph(0.0), Axis(3.0), Graph(1.0), &[ArrowType(NoArrow), Color("red")])
+	.label("x = 3", Axis(3.0), Graph(0.95), &[TextOffset(1.0, 0.0)])
+	.set_legend(Graph(1.0), Character(2.0), &[Placement(AlignRight, AlignBottom)], &[]);
+
+	fg.axes2d()
+	.set_pos_grid(1, 2, 1)
+	.lines(x, y1, &[Color("black")])
+	.label("Figure caption", Screen(0.5), Screen(0.02), &[TextAlign(AlignCenter)]);
+
+	c.show(&mut fg, "fg2.23.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Shapes", &[])
+	.set_x_range(Fix(0.0), Fix(6.0))
+	.set_y_range(Fix(0.0), Fix(30.0))
+	.rectangle(Axis(2.5), Graph(0.0), Axis(3.5), Graph(1.0), &[Color("yellow"), FillAlpha(0.5), Layer(Behind)])
+	.rectangle(Axis(3.6), Axis(11.0), Axis(4.4), Axis(19.0), &[Color("red"), FillPattern(ForwardDiagonals), BorderColor("red"), LineStyle(Dash)])
+	.circle(Axis(5.0), Axis(25.0), Graph(0.05), &[Color("blue"), FillAlpha(0.3), BorderColor("blue")])
+	.ellipse(Axis(1.5), Axis(20.0), Axis(1.5), Axis(5.0), 30.0, &[Color("green"), FillAlpha(0.3)])
+	.polygon(vec![(Graph(0.05), Graph(0.9)), (Graph(0.15), Graph(0.9)), (Graph(0.1), Graph(0.8))].into_iter(), &[Color("black"), Layer(Front)])
+	.lines_points(x, y1, &[Color("black")]);
+
+	c.show(&mut fg, "fg2.24.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Reference lines and spans", &[])
+	.set_legend(Graph(0.05), Graph(0.95), &[Placement(AlignLeft, AlignTop)], &[])
+	.lines_points(x, y1, &[Color("black"), Caption("Data")])
+	.hline(Axis(10.0), "threshold", &[Color("red"), LineStyle(Dash), Caption("Threshold")])
+	.vline(Axis(4.5), "event", &[Color("blue")])
+	.vspan(Axis(2.0), Axis(3.0), "window", &[Color("green"), Caption("Window")])
+	.hspan(Axis(20.0), Axis(25.0), "", &[Color("orange"), FillAlpha(0.2)]);
+
+	c.show(&mut fg, "fg2.25.gnuplot");
 }
 
 fn main()
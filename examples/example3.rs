@@ -86,6 +86,21 @@ fn example(c: Common)
 	.set_view(45.0, 45.0);
 	
 	c.show(&mut fg, "fg3.3.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_pos_grid(1, 2, 0)
+	.set_title("2D Contours", &[])
+	.contour(z1.iter(), w, h, Some((-4.0, -4.0, 4.0, 4.0)), [-0.5f64, -0.25, 0.0, 0.25, 0.5].iter(), true, false, &[Color("black")]);
+
+	fg.axes2d()
+	.set_pos_grid(1, 2, 1)
+	.set_title("Filled 2D Contours", &[])
+	.contour(z1.iter(), w, h, Some((-4.0, -4.0, 4.0, 4.0)), [-1.0f64, -0.5, 0.0, 0.5, 1.0].iter(), false, true, &[Color("black"), LineWidth(2.0)]);
+
+	c.show(&mut fg, "fg3.4.gnuplot");
 }
 
 fn main()
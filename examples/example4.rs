@@ -125,6 +125,91 @@ fn example(c: Common)
 	.set_view(45.0, 45.0);
 
 	c.show(&mut fg, "fg4.5.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes3d()
+	.set_title("Parametric Curve", &[])
+	.parametric_curve((0.0, 6.0 * std::f64::consts::PI), 20, |t| (t.cos(), t.sin(), t / 10.0), &[LineWidth(2.0), Color("red"), Caption("Helix")])
+	.set_view(60.0, 30.0);
+
+	c.show(&mut fg, "fg4.6.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes3d()
+	.set_title("Expression Surface", &[])
+	.set_x_range(Fix(-4.0), Fix(4.0))
+	.set_y_range(Fix(-4.0), Fix(4.0))
+	.set_samples(50, 50)
+	.set_isosamples(50, 50)
+	.expression_surface("cos(x) * cos(y) / (sqrt(x**2 + y**2) + 1)", &[])
+	.set_view(45.0, 45.0);
+
+	c.show(&mut fg, "fg4.7.gnuplot");
+
+	let mut rgb = Vec::with_capacity(3 * zw * zh);
+	for i in 0..zh
+	{
+		for j in 0..zw
+		{
+			rgb.push(255.0 * j as f64 / zw as f64);
+			rgb.push(255.0 * i as f64 / zh as f64);
+			rgb.push(255.0 * (z1[i * zw + j] + 1.0) / 2.0);
+		}
+	}
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("RGB Image", &[])
+	.rgb_image(rgb.iter(), zh, zw, Some((-4.0, -4.0, 4.0, 4.0)), &[]);
+
+	c.show(&mut fg, "fg4.8.gnuplot");
+
+	let freqs: Vec<f64> = (0..zw + 1).map(|i| 10.0f64.powf(1.0 + 3.0 * i as f64 / zw as f64)).collect();
+	let times: Vec<f64> = (0..zh).map(|i| i as f64 * 0.1).collect();
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Non-uniform Image", &[])
+	.set_x_log(Some(10.0))
+	.set_x_label("Frequency", &[])
+	.set_y_label("Time", &[])
+	.image_nonuniform(z1.iter(), zh, zw, &freqs[..], &times[..], &[]);
+
+	c.show(&mut fg, "fg4.9.gnuplot");
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes3d()
+	.set_title("Non-uniform Surface", &[])
+	.set_x_log(Some(10.0))
+	.surface_nonuniform(z1.iter(), zh, zw, &freqs[..], &times[..], &[])
+	.set_view_map();
+
+	c.show(&mut fg, "fg4.10.gnuplot");
+
+	let confusion = [0.9, 0.05, 0.05, 0.1, 0.7, 0.2, 0.0, 0.15, 0.85];
+	let classes = ["Cat", "Dog", "Bird"];
+
+	let mut fg = Figure::new();
+	c.set_term(&mut fg);
+
+	fg.axes2d()
+	.set_title("Annotated Heatmap", &[])
+	.set_palette(GRAY)
+	.set_x_label("Predicted", &[])
+	.set_y_label("Actual", &[])
+	.annotated_heatmap(confusion.iter(), 3, 3, &classes, &classes, |v| format!("{:.2}", v), false, &[]);
+
+	c.show(&mut fg, "fg4.11.gnuplot");
 }
 
 fn main()
@@ -3,17 +3,33 @@
 // All rights reserved. Distributed under LGPL 3.0. For full terms see the file LICENSE.
 
 use std::io::Write;
+use std::f64;
+use std::iter::repeat;
 
 use axes_common::*;
+use contour::*;
 use datatype::*;
 use coordinates::*;
 use options::*;
+use query::*;
+use sampling::*;
 use writer::Writer;
 
 /// 2D axes that is used for drawing 2D plots
 pub struct Axes2D
 {
 	common: AxesCommonData,
+	polar: Option<(ThetaOrigin, bool)>,
+	/// The angle and style of the polar grid. It is written out last, so that `set_grid` doesn't erase it.
+	polar_grid: Option<(AutoOption<f64>, Vec<u8>)>,
+	x2_axis: AxisData,
+	y2_axis: AxisData,
+	r_axis: AxisData,
+	theta_axis: AxisData,
+	/// Positions, texts and values of the cell labels of annotated heatmaps. Their colors depend on the color bar range, so they are written out last.
+	heatmap_labels: Vec<(f64, f64, String, f64)>,
+	/// Positions and levels of the contour line labels. They are formatted like the color bar ticks, so they are written out last.
+	contour_labels: Vec<(f64, f64, f64)>,
 }
 
 impl Axes2D
@@ -56,6 +72,33 @@ impl Axes2D
 		self
 	}
 
+	/// Pads the automatically determined ranges of the X and Y axes, so that the data doesn't touch the border. Has no effect on fixed ranges.
+	///
+	/// # Arguments
+	/// * `left`, `right`, `top`, `bottom` - Padding on each side. Use `AxisOffset` to pad by an amount in data units,
+	///                                      or `GraphOffset` to pad by a fraction of the range (e.g. `GraphOffset(0.05)` for 5%).
+	pub fn set_offsets<'l>(&'l mut self, left: OffsetType, right: OffsetType, top: OffsetType, bottom: OffsetType) -> &'l mut Axes2D
+	{
+		{
+			let c = &mut self.common.commands as &mut Writer;
+			c.write_str("set offsets");
+			for (i, offset) in [left, right, top, bottom].iter().enumerate()
+			{
+				if i > 0
+				{
+					c.write_str(",");
+				}
+				match *offset
+				{
+					AxisOffset(v) => write!(c, " first {:.12e}", v),
+					GraphOffset(v) => write!(c, " graph {:.12e}", v),
+				};
+			}
+			c.write_str("\n");
+		}
+		self
+	}
+
 	fn set_axis_common<'l>(&'l mut self, axis: &str, show: bool, options: &[PlotOption]) -> &'l mut Axes2D
 	{
 		{
@@ -100,6 +143,217 @@ impl Axes2D
 		self.set_axis_common("y", show, options)
 	}
 
+	/// Like `set_x_label`, but for the secondary (top) X axis
+	pub fn set_x2_label<'l>(&'l mut self, text: &str, options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.common.set_label_common(X2Label, text, options);
+		self
+	}
+
+	/// Like `set_x_label`, but for the secondary (right) Y axis
+	pub fn set_y2_label<'l>(&'l mut self, text: &str, options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.common.set_label_common(Y2Label, text, options);
+		self
+	}
+
+	/// Like `set_x_ticks` but for the secondary (top) X axis. The secondary axes have no ticks by default. Note that the ticks of the
+	/// primary X axis are mirrored onto the top border by default, so you'll want to pass `Mirror(false)` to `set_x_ticks` when using these.
+	pub fn set_x2_ticks<'l>(&'l mut self, tick_placement: Option<(AutoOption<f64>, u32)>, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.x2_axis.set_ticks(tick_placement, tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_ticks` but for the secondary (right) Y axis. The secondary axes have no ticks by default. Note that the ticks of the
+	/// primary Y axis are mirrored onto the right border by default, so you'll want to pass `Mirror(false)` to `set_y_ticks` when using these.
+	pub fn set_y2_ticks<'l>(&'l mut self, tick_placement: Option<(AutoOption<f64>, u32)>, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.y2_axis.set_ticks(tick_placement, tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_ticks_custom` but for the secondary (top) X axis.
+	pub fn set_x2_ticks_custom<'l, T: DataType, TL: Iterator<Item = Tick<T>>>(&'l mut self, ticks: TL, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.x2_axis.set_ticks_custom(ticks, tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_ticks_custom` but for the secondary (right) Y axis.
+	pub fn set_y2_ticks_custom<'l, T: DataType, TL: Iterator<Item = Tick<T>>>(&'l mut self, ticks: TL, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.y2_axis.set_ticks_custom(ticks, tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_tick_format` but for the secondary (top) X axis.
+	pub fn set_x2_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Axes2D
+	{
+		self.x2_axis.set_format(format);
+		self
+	}
+
+	/// Like `set_x_tick_format` but for the secondary (right) Y axis.
+	pub fn set_y2_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Axes2D
+	{
+		self.y2_axis.set_format(format);
+		self
+	}
+
+	/// Like `set_x_tick_formatter` but for the secondary (top) X axis.
+	pub fn set_x2_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.x2_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_tick_formatter` but for the secondary (right) Y axis.
+	pub fn set_y2_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.y2_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
+	/// Set the range of values for the secondary (top) X axis. Plot elements are plotted against this axis when they are given the `Axes(X2, ..)` option.
+	///
+	/// # Arguments
+	/// * `min` - Minimum X value
+	/// * `max` - Maximum X value
+	pub fn set_x2_range<'l>(&'l mut self, min: AutoOption<f64>, max: AutoOption<f64>) -> &'l mut Axes2D
+	{
+		self.x2_axis.set_range(min, max);
+		self
+	}
+
+	/// Set the range of values for the secondary (right) Y axis. Plot elements are plotted against this axis when they are given the `Axes(.., Y2)` option.
+	///
+	/// # Arguments
+	/// * `min` - Minimum Y value
+	/// * `max` - Maximum Y value
+	pub fn set_y2_range<'l>(&'l mut self, min: AutoOption<f64>, max: AutoOption<f64>) -> &'l mut Axes2D
+	{
+		self.y2_axis.set_range(min, max);
+		self
+	}
+
+	/// Sets the secondary (top) X axis be logarithmic. Note that the range must be non-negative for this to be valid.
+	///
+	/// # Arguments
+	/// * `base` - If Some, then specifies base of the logarithm, if None makes the axis not be logarithmic
+	pub fn set_x2_log<'l>(&'l mut self, base: Option<f64>) -> &'l mut Axes2D
+	{
+		self.x2_axis.set_log(base);
+		self
+	}
+
+	/// Sets the secondary (right) Y axis be logarithmic. Note that the range must be non-negative for this to be valid.
+	///
+	/// # Arguments
+	/// * `base` - If Some, then specifies base of the logarithm, if None makes the axis not be logarithmic
+	pub fn set_y2_log<'l>(&'l mut self, base: Option<f64>) -> &'l mut Axes2D
+	{
+		self.y2_axis.set_log(base);
+		self
+	}
+
+	/// Switches the axes to polar mode. In this mode, the x values passed to the plotting methods (e.g. `lines`, `points` and `fill_between`)
+	/// are interpreted as the angle (theta, in degrees) and the y values as the radius (r).
+	///
+	/// # Arguments
+	/// * `origin` - Where the zero angle is located
+	/// * `clockwise` - Whether the angle increases clockwise or counter-clockwise
+	pub fn set_polar<'l>(&'l mut self, origin: ThetaOrigin, clockwise: bool) -> &'l mut Axes2D
+	{
+		self.polar = Some((origin, clockwise));
+		self
+	}
+
+	/// Draws a polar grid, consisting of circles at the positions of the radial ticks and radial lines spaced at a certain angle.
+	/// Only has an effect when the axes are in polar mode (see `set_polar`). This can be combined with `set_grid` in any order, in which case
+	/// all the grid lines are styled by the options passed to `set_grid`, as gnuplot has only one grid style.
+	///
+	/// # Arguments
+	/// * `angle` - The angle between the radial grid lines (in degrees). Set to `Auto` to let gnuplot decide the spacing
+	/// * `options` - Array of PlotOption controlling the appearance of the grid. Relevant options are:
+	///      * `Color` - Specifies the color of the grid lines
+	///      * `LineStyle` - Specifies the style of the grid lines
+	///      * `LineWidth` - Specifies the width of the grid lines
+	pub fn set_polar_grid<'l>(&'l mut self, angle: AutoOption<f64>, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let mut style = vec![];
+		AxesCommonData::write_color_options(&mut style, options, Some("gray"));
+		AxesCommonData::write_line_options(&mut style, options);
+		self.polar_grid = Some((angle, style));
+		self
+	}
+
+	/// Set the range of values for the radial axis. Only has an effect when the axes are in polar mode (see `set_polar`).
+	///
+	/// # Arguments
+	/// * `min` - Minimum radius
+	/// * `max` - Maximum radius
+	pub fn set_r_range<'l>(&'l mut self, min: AutoOption<f64>, max: AutoOption<f64>) -> &'l mut Axes2D
+	{
+		self.r_axis.set_range(min, max);
+		self
+	}
+
+	/// Like `set_x_ticks` but for the radial axis. Only has an effect when the axes are in polar mode (see `set_polar`).
+	pub fn set_r_ticks<'l>(&'l mut self, tick_placement: Option<(AutoOption<f64>, u32)>, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.r_axis.set_ticks(tick_placement, tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_ticks_custom` but for the radial axis. Only has an effect when the axes are in polar mode (see `set_polar`).
+	pub fn set_r_ticks_custom<'l, T: DataType, TL: Iterator<Item = Tick<T>>>(&'l mut self, ticks: TL, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.r_axis.set_ticks_custom(ticks, tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_tick_format` but for the radial axis. Only has an effect when the axes are in polar mode (see `set_polar`).
+	pub fn set_r_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Axes2D
+	{
+		self.r_axis.set_format(format);
+		self
+	}
+
+	/// Like `set_x_tick_formatter` but for the radial axis. Only has an effect when the axes are in polar mode (see `set_polar`).
+	pub fn set_r_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.r_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
+	/// Sets the radial axis be logarithmic. Only has an effect when the axes are in polar mode (see `set_polar`).
+	///
+	/// # Arguments
+	/// * `base` - If Some, then specifies base of the logarithm, if None makes the axis not be logarithmic
+	pub fn set_r_log<'l>(&'l mut self, base: Option<f64>) -> &'l mut Axes2D
+	{
+		self.r_axis.set_log(base);
+		self
+	}
+
+	/// Sets the properties of the angular ticks, drawn around the perimeter of the plot. Only has an effect when the axes are in polar mode (see `set_polar`).
+	///
+	/// # Arguments
+	/// * `tick_placement` - Controls the placement of the ticks. Pass `None` to hide the ticks. Otherwise, the first tuple value controls the spacing
+	///                      of the major ticks (in degrees), otherwise set it to `Auto` to let gnuplot decide the spacing automatically. The second
+	///                      tuple value is ignored, as there are no minor angular ticks.
+	/// * `tick_options` - Array of TickOption controlling the appearance of the ticks
+	/// * `label_options` - Array of LabelOption controlling the appearance of the tick labels. Relevant options are:
+	///      * `Offset` - Specifies the offset of the label
+	///      * `Font` - Specifies the font of the label
+	///      * `TextColor` - Specifies the color of the label
+	pub fn set_theta_ticks<'l>(&'l mut self, tick_placement: Option<(AutoOption<f64>, u32)>, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes2D
+	{
+		self.theta_axis.set_ticks(tick_placement.map(|(incr, _)| (incr, 0)), tick_options, label_options);
+		self
+	}
+
 	/// Adds an arrow to the plot. The arrow is drawn from `(x1, y1)` to `(x2, y2)` with the arrow point towards `(x2, y2)`.
 	/// # Arguments
 	/// * `x1` - X coordinate of the arrow start
@@ -152,6 +406,107 @@ impl Axes2D
 		self
 	}
 
+	/// Draws a horizontal line across the whole plot. The line stays at the same Y value when the range of the X axis changes.
+	/// # Arguments
+	/// * `y` - Y coordinate of the line (e.g. `Axis(threshold)`)
+	/// * `label` - Text drawn above the right end of the line. Pass an empty string to omit it
+	/// * `options` - Array of PlotOption controlling the appearance of the line. Relevant options are:
+	///      * `Caption` - Adds an entry for the line to the legend. Requires gnuplot 5.2.6 or later
+	///      * `Color` - Specifies the color of the line and its label
+	///      * `LineStyle` - Specifies the style of the line
+	///      * `LineWidth` - Specifies the width of the line
+	pub fn hline<'l>(&'l mut self, y: Coordinate, label: &str, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.reference_line(Graph(0.0), y, Graph(1.0), y, options);
+		if !label.is_empty()
+		{
+			let color = line_color(options);
+			self.common.set_label_common(Label(Graph(0.99), y), label, &[TextAlign(AlignRight), TextOffset(0.0, 0.5), TextColor(color)]);
+		}
+		self
+	}
+
+	/// Draws a vertical line across the whole plot. The line stays at the same X value when the range of the Y axis changes.
+	/// # Arguments
+	/// * `x` - X coordinate of the line (e.g. `Axis(t0)`)
+	/// * `label` - Text drawn along the top of the line. Pass an empty string to omit it
+	/// * `options` - Array of PlotOption controlling the appearance of the line. See `hline` for the relevant options
+	pub fn vline<'l>(&'l mut self, x: Coordinate, label: &str, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.reference_line(x, Graph(0.0), x, Graph(1.0), options);
+		if !label.is_empty()
+		{
+			let color = line_color(options);
+			self.common.set_label_common(Label(x, Graph(0.99)), label, &[TextAlign(AlignRight), Rotate(90.0), TextOffset(-1.0, 0.0), TextColor(color)]);
+		}
+		self
+	}
+
+	/// Shades a horizontal band across the whole plot. The band stays at the same Y values when the range of the X axis changes.
+	/// # Arguments
+	/// * `y1` - Y coordinate of one edge of the band (e.g. `Axis(lo)`)
+	/// * `y2` - Y coordinate of the other edge of the band
+	/// * `label` - Text drawn inside the band, next to the `y1` edge at the left of the plot. Pass an empty string to omit it
+	/// * `options` - Array of PlotOption controlling the appearance of the band. Relevant options are:
+	///      * `Caption` - Adds an entry for the band to the legend. Requires gnuplot 5.2.6 or later
+	///      * `Color` - Specifies the fill color (gray by default)
+	///      * `FillAlpha` - Sets the transparency of the fill (0.3 by default)
+	///      * `FillPattern` - Fills the band with a pattern instead
+	///      * `BorderColor` - Draws a border with this color. Without it, no border is drawn
+	///      * `Layer` - Sets whether the band is drawn in front of or behind (default) the plot elements
+	pub fn hspan<'l>(&'l mut self, y1: Coordinate, y2: Coordinate, label: &str, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.reference_span(Graph(0.0), y1, Graph(1.0), y2, options);
+		if !label.is_empty()
+		{
+			self.common.set_label_common(Label(Graph(0.01), y1), label, &[TextAlign(AlignLeft), TextOffset(0.0, 0.7)]);
+		}
+		self
+	}
+
+	/// Shades a vertical band across the whole plot. The band stays at the same X values when the range of the Y axis changes.
+	/// # Arguments
+	/// * `x1` - X coordinate of one edge of the band (e.g. `Axis(t0)`)
+	/// * `x2` - X coordinate of the other edge of the band (e.g. `Axis(t1)`)
+	/// * `label` - Text drawn inside the band, next to the `x1` edge at the top of the plot. Pass an empty string to omit it
+	/// * `options` - Array of PlotOption controlling the appearance of the band. See `hspan` for the relevant options
+	pub fn vspan<'l>(&'l mut self, x1: Coordinate, x2: Coordinate, label: &str, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.reference_span(x1, Graph(0.0), x2, Graph(1.0), options);
+		if !label.is_empty()
+		{
+			self.common.set_label_common(Label(x1, Graph(0.95)), label, &[TextAlign(AlignLeft), TextOffset(0.5, 0.0)]);
+		}
+		self
+	}
+
+	fn reference_line(&mut self, x1: Coordinate, y1: Coordinate, x2: Coordinate, y2: Coordinate, options: &[PlotOption])
+	{
+		// The legend entry gets the same defaults as the line, so that its swatch matches it
+		let mut line_options = options.to_vec();
+		line_options.push(Color(line_color(options)));
+		line_options.push(ArrowType(NoArrow));
+		self.arrow(x1, y1, x2, y2, &line_options);
+		if has_caption(options)
+		{
+			// A legend entry without any data, so that it doesn't affect the ranges of the axes
+			self.common.plot_expression(Lines, false, "keyentry", &line_options);
+		}
+	}
+
+	fn reference_span(&mut self, x1: Coordinate, y1: Coordinate, x2: Coordinate, y2: Coordinate, options: &[PlotOption])
+	{
+		let mut span_options = options.to_vec();
+		span_options.push(Color("gray"));
+		span_options.push(FillAlpha(0.3));
+		span_options.push(Layer(Back));
+		self.rectangle(x1, y1, x2, y2, &span_options);
+		if has_caption(options)
+		{
+			self.common.plot_expression(FillBetween, false, "keyentry", &span_options);
+		}
+	}
+
 	/// Specifies the location and other properties of the legend
 	/// # Arguments
 	/// * `x` - X coordinate of the legend
@@ -290,12 +645,109 @@ impl Axes2D
 	///     * `LineWidth` - Sets the width of the line
 	///     * `LineStyle` - Sets the style of the line
 	///     * `Color` - Sets the color
+	///     * `Smooth` - Smooths the data before plotting it
 	pub fn lines<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
 	{
 		self.common.plot2(Lines, x, y, options);
 		self
 	}
 
+	/// Plot a function of x as a line. The function is sampled when the plot is shown or echoed, once the range of the X axis is known:
+	/// it is first evaluated at evenly spaced values of x, after which additional samples are added where the curve bends sharply.
+	/// Values of x where the function is not finite, as well as jump discontinuities, create gaps in the line.
+	/// If the X axis is logarithmic (see `set_x_log`), the samples are evenly spaced in the logarithm of x, and the range must be positive
+	/// (showing the plot panics otherwise).
+	/// # Arguments
+	/// * `f` - The function to plot
+	/// * `x_range` - The range of x values to sample. `Auto` uses the corresponding end of the X axis range if it has been fixed via `set_x_range`,
+	///               otherwise the extent of the data plotted against the X axis by the other plot elements. If there is no such data, gnuplot's
+	///               default of `[-10:10]` (or `[1:10]` for logarithmic axes) is used.
+	/// * `samples` - Number of initial evenly spaced samples
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn function<'l, F: Fn(f64) -> f64 + 'static>(&'l mut self, f: F, x_range: (AutoOption<f64>, AutoOption<f64>), samples: usize, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot_function(Box::new(f), x_range, samples, options);
+		self
+	}
+
+	/// Plot a gnuplot expression of `x` (e.g. `"sin(x) * exp(-x / 5)"`) as a line. Unlike the `function` method, the expression is evaluated by gnuplot itself,
+	/// so it is re-sampled whenever the view changes (e.g. when zooming in an interactive terminal). The number of samples is controlled by `set_samples`.
+	/// # Arguments
+	/// * `expr` - The expression to plot, in gnuplot syntax
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn expression<'l>(&'l mut self, expr: &str, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot_expression(Lines, false, expr, options);
+		self
+	}
+
+	/// Fit a model to data using gnuplot's `fit` command, which runs a separate gnuplot process and waits for it to finish. Requires gnuplot 5.0 or later.
+	/// Points where either coordinate is not finite are skipped.
+	///
+	/// Unlike the other methods, this one returns the fit result rather than the axes. If the fit fails (e.g. because it did not converge, or the model is
+	/// not valid gnuplot syntax) the error message reported by gnuplot is returned instead.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `model_expr` - The model as a gnuplot expression of `x` and the parameters (e.g. `"a * exp(-x / b)"`). It must be a single expression,
+	///                  without newlines, `;` or `#`, otherwise an error is returned
+	/// * `initial_params` - Names and initial values of the parameters that are varied by the fit. The names must be valid gnuplot variable names
+	///                      (letters, digits and underscores, not starting with a digit) other than `x`, otherwise an error is returned
+	/// * `overlay` - If not `None`, the fitted curve is added to the axes with these options, see `expression` for the relevant ones
+	pub fn fit<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, model_expr: &str, initial_params: &[(&str, f64)], overlay: Option<&[PlotOption]>) -> Result<FitResult, String>
+	{
+		if initial_params.is_empty()
+		{
+			return Err("No parameters to fit".to_string());
+		}
+
+		let (xs, ys): (Vec<f64>, Vec<f64>) = x.zip(y).map(|(x, y)| (x.get(), y.get())).filter(|&(x, y)| x.is_finite() && y.is_finite()).unzip();
+		let values = run_gnuplot(&fit_script(&xs, &ys, model_expr, initial_params)?)?;
+		let res = parse_fit(&values, model_expr, initial_params)?;
+
+		match overlay
+		{
+			Some(options) =>
+			{
+				self.expression(&res.expression(), options);
+			},
+			None => ()
+		}
+		Ok(res)
+	}
+
+	/// Plot a parametric curve, specified by a function of a parameter `t`, as a line. The function is first evaluated at evenly spaced values
+	/// of `t`, after which additional samples are added where the curve bends sharply, so that the curve looks smooth without having to pick
+	/// the number of samples by hand. Non-finite values of the function create gaps in the line.
+	/// # Arguments
+	/// * `t_range` - The first and last values of `t`
+	/// * `samples` - Number of initial evenly spaced samples
+	/// * `f` - Function that maps `t` to the x and y coordinates of the curve
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn parametric<'l, F: FnMut(f64) -> (f64, f64)>(&'l mut self, t_range: (f64, f64), samples: usize, mut f: F, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let (t_min, t_max) = t_range;
+		let points = sample_curve(t_min, t_max, samples, |t|
+		{
+			let (x, y) = f(t);
+			[x, y, 0.0]
+		});
+		self.common.plot2(Lines, points.iter().map(|p| p[0]), points.iter().map(|p| p[1]), options);
+		self
+	}
+
 	/// Plot a 2D scatter-plot with a point standing in for each data point
 	/// # Arguments
 	/// * `x` - Iterator for the x values
@@ -305,12 +757,53 @@ impl Axes2D
 	///     * `PointSymbol` - Sets symbol for each point
 	///     * `PointSize` - Sets the size of each point
 	///     * `Color` - Sets the color
+	///     * `Smooth` - Smooths the data before plotting it
 	pub fn points<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
 	{
 		self.common.plot2(Points, x, y, options);
 		self
 	}
 
+	/// Plot a 2D scatter-plot where the color and the size of each point are taken from the data.
+	/// The color values are mapped to colors using the palette (see `set_palette`), with the range of the mapping controlled by the color bar axis.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `color_values` - Iterator for the values that are mapped to the color of each point
+	/// * `size_values` - Iterator for the size of each point. The size acts as a multiplier, with 1.0 being the default.
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `PointSymbol` - Sets symbol for each point
+	pub fn scatter<'l,
+	               Tx: DataType, X: Iterator<Item = Tx>,
+	               Ty: DataType, Y: Iterator<Item = Ty>,
+	               Tc: DataType, C: Iterator<Item = Tc>,
+	               Ts: DataType, S: Iterator<Item = Ts>>(&'l mut self, x: X, y: Y, color_values: C, size_values: S, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot4(PalettePoints, x, y, size_values, color_values, options);
+		self
+	}
+
+	/// Like `scatter`, but the color of each point is specified explicitly.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `colors` - Iterator for the color of each point, specified as a tuple of red, green and blue components
+	/// * `size_values` - Iterator for the size of each point. The size acts as a multiplier, with 1.0 being the default.
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `PointSymbol` - Sets symbol for each point
+	pub fn scatter_rgb<'l,
+	                   Tx: DataType, X: Iterator<Item = Tx>,
+	                   Ty: DataType, Y: Iterator<Item = Ty>,
+	                   C: Iterator<Item = (u8, u8, u8)>,
+	                   Ts: DataType, S: Iterator<Item = Ts>>(&'l mut self, x: X, y: Y, colors: C, size_values: S, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let colors = colors.map(|(r, g, b)| ((r as u32) << 16 | (g as u32) << 8 | b as u32) as f64);
+		self.common.plot4(RGBPoints, x, y, size_values, colors, options);
+		self
+	}
+
 	/// A combination of lines and points methods (drawn in that order).
 	/// # Arguments
 	/// * `x` - Iterator for the x values
@@ -387,25 +880,150 @@ impl Axes2D
 		self
 	}
 
-	/// Plot a 2D scatter-plot using boxes of automatic width. Box widths are set so that there are no gaps between successive boxes (i.e. each box may have a different width).
-	/// Boxes start at the x-axis and go towards the y value of the datapoint.
+	/// Like `fill_between`, but the two curves are drawn as steps (in the same manner as the `steps` method) rather than straight lines
+	/// connecting the data points. Useful for drawing confidence bands around step-shaped data.
 	/// # Arguments
-	/// * `x` - Iterator for the x values (center of the box)
-	/// * `y` - Iterator for the y values
+	/// * `x` - Iterator for the x values
+	/// * `y_lo` - Iterator for the bottom y values
+	/// * `y_hi` - Iterator for the top y values
 	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
 	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
-	///     * `LineWidth` - Sets the width of the border
-	///     * `LineStyle` - Sets the style of the border
-	///     * `BorderColor` - Sets the color of the border
-	///     * `Color` - Sets the color of the box fill
-	///     * `FillAlpha` - Sets the transparency of the box fill
-	pub fn boxes<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	///     * `FillRegion` - Specifies the region between the two curves to fill
+	///     * `Color` - Sets the color of the filled region
+	///     * `FillAlpha` - Sets the transparency of the filled region
+	pub fn fill_between_steps<'l,
+	                   Tx: DataType, X: Iterator<Item = Tx>,
+	                   Tyl: DataType, YL: Iterator<Item = Tyl>,
+	                   Tyh: DataType, YH: Iterator<Item = Tyh>>(&'l mut self, x: X, y_lo: YL, y_hi: YH, options: &[PlotOption]) -> &'l mut Axes2D
 	{
-		self.common.plot2(Boxes, x, y, options);
+		let mut step_x = vec![];
+		let mut step_lo = vec![];
+		let mut step_hi = vec![];
+		let mut prev: Option<(f64, f64)> = None;
+		for ((x, lo), hi) in x.zip(y_lo).zip(y_hi)
+		{
+			let (x, lo, hi) = (x.get(), lo.get(), hi.get());
+			if let Some((prev_lo, prev_hi)) = prev
+			{
+				step_x.push(x);
+				step_lo.push(prev_lo);
+				step_hi.push(prev_hi);
+			}
+			step_x.push(x);
+			step_lo.push(lo);
+			step_hi.push(hi);
+			prev = Some((lo, hi));
+		}
+		self.common.plot3(FillBetween, step_x.iter(), step_lo.iter(), step_hi.iter(), options);
 		self
 	}
 
-	/// Plot a 2D scatter-plot using boxes of set (per box) width.
+	/// Plot a 2D scatter-plot with the data points connected by steps. The step from each point is horizontal first, then vertical.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn steps<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot2(Steps, x, y, options);
+		self
+	}
+
+	/// Like `steps`, but the step from each point is vertical first, then horizontal.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn fsteps<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot2(FSteps, x, y, options);
+		self
+	}
+
+	/// Like `steps`, but each data point is at the center of its horizontal step, with the vertical steps placed halfway between
+	/// successive points. This is useful for plotting histograms.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn histeps<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot2(HiSteps, x, y, options);
+		self
+	}
+
+	/// Plot a 2D scatter-plot with a vertical line drawn from the x-axis to each data point.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the lines
+	///     * `LineStyle` - Sets the style of the lines
+	///     * `Color` - Sets the color
+	pub fn impulses<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot2(Impulses, x, y, options);
+		self
+	}
+
+	/// A stem plot: a combination of the impulses and points methods (drawn in that order). Only a single legend entry is created.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `PointSymbol` - Sets symbol for each point
+	///     * `PointSize` - Sets the size of each point
+	///     * `LineWidth` - Sets the width of the lines
+	///     * `LineStyle` - Sets the style of the lines
+	///     * `Color` - Sets the color
+	pub fn stem<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let x: Vec<f64> = x.map(|v| v.get()).collect();
+		let y: Vec<f64> = y.map(|v| v.get()).collect();
+		let uncaptioned: Vec<PlotOption> = options.iter().filter(|o| match **o
+		{
+			Caption(..) => false,
+			_ => true
+		}).cloned().collect();
+
+		self.common.plot2(Impulses, x.iter(), y.iter(), &uncaptioned[..]);
+		self.common.plot2(Points, x.iter(), y.iter(), options);
+		self
+	}
+
+	/// Plot a 2D scatter-plot using boxes of automatic width. Box widths are set so that there are no gaps between successive boxes (i.e. each box may have a different width).
+	/// Boxes start at the x-axis and go towards the y value of the datapoint.
+	/// # Arguments
+	/// * `x` - Iterator for the x values (center of the box)
+	/// * `y` - Iterator for the y values
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the border
+	///     * `LineStyle` - Sets the style of the border
+	///     * `BorderColor` - Sets the color of the border
+	///     * `Color` - Sets the color of the box fill
+	///     * `FillAlpha` - Sets the transparency of the box fill
+	pub fn boxes<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot2(Boxes, x, y, options);
+		self
+	}
+
+	/// Plot a 2D scatter-plot using boxes of set (per box) width.
 	/// Boxes start at the x-axis and go towards the y value of the datapoint.
 	/// # Arguments
 	/// * `x` - Iterator for the x values (center of the box)
@@ -430,6 +1048,280 @@ impl Axes2D
 		self
 	}
 
+	/// Plot a 2D scatter-plot with a circle drawn around each data point (e.g. a bubble chart).
+	/// # Arguments
+	/// * `x` - Iterator for the x values (center of the circle)
+	/// * `y` - Iterator for the y values (center of the circle)
+	/// * `r` - Iterator for the radius of each circle, in units of the X axis
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the border
+	///     * `LineStyle` - Sets the style of the border
+	///     * `BorderColor` - Sets the color of the border
+	///     * `Color` - Sets the color of the circle fill
+	///     * `FillAlpha` - Sets the transparency of the circle fill
+	pub fn circles<'l,
+	               Tx: DataType, X: Iterator<Item = Tx>,
+	               Ty: DataType, Y: Iterator<Item = Ty>,
+	               Tr: DataType, R: Iterator<Item = Tr>>(&'l mut self, x: X, y: Y, r: R, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot3(Circles, x, y, r, options);
+		self
+	}
+
+	/// Like `circles`, but all the circles have the same radius
+	/// # Arguments
+	/// * `x` - Iterator for the x values (center of the circle)
+	/// * `y` - Iterator for the y values (center of the circle)
+	/// * `radius` - Radius of the circles, in units of the X axis
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the border
+	///     * `LineStyle` - Sets the style of the border
+	///     * `BorderColor` - Sets the color of the border
+	///     * `Color` - Sets the color of the circle fill
+	///     * `FillAlpha` - Sets the transparency of the circle fill
+	pub fn circles_fixed_radius<'l, Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&'l mut self, x: X, y: Y, radius: f64, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot3(Circles, x, y, repeat(radius), options);
+		self
+	}
+
+	/// Plot a 2D scatter-plot with an ellipse drawn around each data point (e.g. covariance ellipses).
+	/// # Arguments
+	/// * `x` - Iterator for the x values (center of the ellipse)
+	/// * `y` - Iterator for the y values (center of the ellipse)
+	/// * `major` - Iterator for the length of the major diameter of each ellipse, in units of the X axis by default
+	/// * `minor` - Iterator for the length of the minor diameter of each ellipse, in units of the Y axis by default
+	/// * `angle` - Iterator for the angle of the major diameter of each ellipse, in degrees counter-clockwise from the X axis
+	/// * `options` - Array of PlotOption controlling the appearance of the plot element. The relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `EllipseUnits` - Sets which axes the diameters are measured in
+	///     * `LineWidth` - Sets the width of the border
+	///     * `LineStyle` - Sets the style of the border
+	///     * `BorderColor` - Sets the color of the border
+	///     * `Color` - Sets the color of the ellipse fill
+	///     * `FillAlpha` - Sets the transparency of the ellipse fill
+	pub fn ellipses<'l,
+	                Tx: DataType, X: Iterator<Item = Tx>,
+	                Ty: DataType, Y: Iterator<Item = Ty>,
+	                Tma: DataType, MA: Iterator<Item = Tma>,
+	                Tmi: DataType, MI: Iterator<Item = Tmi>,
+	                Ta: DataType, A: Iterator<Item = Ta>>(&'l mut self, x: X, y: Y, major: MA, minor: MI, angle: A, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot5(Ellipses, x, y, major, minor, angle, options);
+		self
+	}
+
+	/// Plot a bar chart of one or more series of values over a set of categories. The categories are placed at integer positions
+	/// (starting at 0) along the X axis (or the Y axis for horizontal bar charts), with their names used as the tick labels.
+	/// Each series gets its own color and legend entry.
+	/// # Arguments
+	/// * `categories` - Names of the categories
+	/// * `series` - Array of tuples of the series caption and its values (one per category)
+	/// * `mode` - How to lay out the bars of the different series. See `BarMode`.
+	/// * `horizontal` - Whether the bars extend horizontally rather than vertically
+	/// * `options` - Array of PlotOption controlling the appearance of the bars. The relevant options are:
+	///     * `LineWidth` - Sets the width of the border
+	///     * `LineStyle` - Sets the style of the border
+	///     * `BorderColor` - Sets the color of the border
+	///     * `FillAlpha` - Sets the transparency of the bar fill
+	pub fn bar_chart<'l>(&'l mut self, categories: &[&str], series: &[(&str, &[f64])], mode: BarMode, horizontal: bool, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let num_series = series.len();
+		let totals: Vec<f64> = (0..categories.len()).map(|j|
+		{
+			series.iter().map(|&(_, values)| values.get(j).map_or(0.0, |v| v.abs())).fold(0.0, |a, b| a + b)
+		}).collect();
+		let mut pos_base = vec![0.0; categories.len()];
+		let mut neg_base = vec![0.0; categories.len()];
+
+		for (i, &(caption, values)) in series.iter().enumerate()
+		{
+			let mut centers = vec![];
+			let mut mids = vec![];
+			let mut half_widths = vec![];
+			let mut half_heights = vec![];
+			for (j, &v) in values.iter().enumerate().take(categories.len())
+			{
+				let (center, half_width, lo, hi) = match mode
+				{
+					Grouped =>
+					{
+						let width = 0.8 / num_series as f64;
+						(j as f64 - 0.4 + width * (i as f64 + 0.5), 0.5 * width, 0.0, v)
+					},
+					Stacked | PercentStacked =>
+					{
+						let v = match mode
+						{
+							PercentStacked if totals[j] > 0.0 => 100.0 * v / totals[j],
+							_ => v
+						};
+						let base = if v < 0.0
+						{
+							&mut neg_base[j]
+						}
+						else
+						{
+							&mut pos_base[j]
+						};
+						let lo = *base;
+						*base += v;
+						(j as f64, 0.4, lo, lo + v)
+					}
+				};
+				centers.push(center);
+				half_widths.push(half_width);
+				mids.push(0.5 * (lo + hi));
+				half_heights.push(0.5 * (hi - lo).abs());
+			}
+
+			let mut series_options = vec![Caption(caption), Color(SERIES_COLORS[i % SERIES_COLORS.len()])];
+			series_options.extend(options.iter().cloned());
+			if horizontal
+			{
+				self.common.plot4(BoxXYError, mids.iter(), centers.iter(), half_heights.iter(), half_widths.iter(), &series_options[..]);
+			}
+			else
+			{
+				self.common.plot4(BoxXYError, centers.iter(), mids.iter(), half_widths.iter(), half_heights.iter(), &series_options[..]);
+			}
+		}
+
+		{
+			let axis = if horizontal
+			{
+				&mut self.common.y_axis
+			}
+			else
+			{
+				&mut self.common.x_axis
+			};
+			axis.set_ticks_custom(categories.iter().enumerate().map(|(j, c)| Major(j as f64, Fix(c.replace("%", "%%")))), &[], &[]);
+			axis.set_range(Fix(-0.5), Fix(categories.len() as f64 - 0.5));
+		}
+		self
+	}
+
+	/// Plot a pie chart, with each value drawn as a slice proportional to its share of the total. The slices are drawn counter-clockwise,
+	/// each with its own color and legend entry. The pie has a radius of 1 and is centered at the origin.
+	///
+	/// To make the pie round and fit it in the view, this changes settings of the whole axes: the aspect ratio is set to 1 (`set size ratio -1`),
+	/// the X and Y ranges are fixed around the pie, and the axis ticks and the border are hidden. These also apply to any other element plotted
+	/// on the same axes, and can be overridden by calling `set_aspect_ratio`, `set_x_range`, `set_y_range`, `set_x_ticks`, `set_y_ticks` and
+	/// `set_border` after this method.
+	/// # Arguments
+	/// * `values` - Iterator for the values of the slices. The values must be non-negative
+	/// * `labels` - Names of the slices, used as their legend entries
+	/// * `pie_options` - Array of PieOption controlling the layout of the pie
+	/// * `options` - Array of PlotOption controlling the appearance of the slices. The relevant options are:
+	///     * `LineWidth` - Sets the width of the border
+	///     * `LineStyle` - Sets the style of the border
+	///     * `BorderColor` - Sets the color of the border. By default, the border has the color of the slice
+	///     * `FillAlpha` - Sets the transparency of the slice fill
+	pub fn pie<'l, T: DataType, V: Iterator<Item = T>>(&'l mut self, values: V, labels: &[&str], pie_options: &[PieOption], options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let values: Vec<f64> = values.map(|v| v.get()).collect();
+		let total = values.iter().fold(0.0, |a, &b| a + b);
+
+		let mut angle = 90.0;
+		first_opt!{pie_options,
+			StartAngle(a) =>
+			{
+				angle = a;
+			}
+		}
+		let mut hole = 0.0;
+		first_opt!{pie_options,
+			HoleRadius(r) =>
+			{
+				assert!((0.0..1.0).contains(&r), "Hole radius must be between 0 and 1");
+				hole = r;
+			}
+		}
+		let mut percent_labels = false;
+		first_opt!{pie_options,
+			PercentLabels =>
+			{
+				percent_labels = true;
+			}
+		}
+
+		let mut max_explode: f64 = 0.0;
+		for (i, &v) in values.iter().enumerate()
+		{
+			assert!(v >= 0.0, "Pie chart values must be non-negative");
+			let sweep = if total > 0.0 { 360.0 * v / total } else { 0.0 };
+			let mid = (angle + 0.5 * sweep).to_radians();
+
+			let mut explode = 0.0;
+			for o in pie_options.iter()
+			{
+				match *o
+				{
+					Explode(idx, e) if idx == i => explode = e,
+					_ => ()
+				}
+			}
+			max_explode = max_explode.max(explode);
+
+			let (x, y) = (explode * mid.cos(), explode * mid.sin());
+			let mut wedge_options = vec![Caption(labels.get(i).map_or("", |l| *l)), Color(SERIES_COLORS[i % SERIES_COLORS.len()])];
+			wedge_options.extend(options.iter().cloned());
+			if sweep > 0.0
+			{
+				self.common.plot5(Circles, Some(x).iter(), Some(y).iter(), Some(1.0).iter(), Some(angle).iter(), Some(angle + sweep).iter(), &wedge_options[..]);
+
+				if hole > 0.0
+				{
+					// Covers the inner part of this wedge only, so that the hole follows it when it is exploded
+					let c = &mut self.common.commands as &mut Writer;
+					write!(c, "set object circle at{},{} size{} arc [{:.12e}:{:.12e}] front fc bgnd fs solid 1", Axis(x), Axis(y), Axis(hole), angle, angle + sweep);
+					let mut have_border = false;
+					first_opt!{options,
+						BorderColor(s) =>
+						{
+							write!(c, r#" border rgb "{}""#, s);
+							have_border = true;
+						}
+					}
+					if have_border
+					{
+						AxesCommonData::write_line_options(c, options);
+					}
+					else
+					{
+						c.write_str(" noborder");
+					}
+					c.write_str("\n");
+				}
+			}
+			else
+			{
+				// An arc with equal start and end angles would be a full circle, so only add the legend entry
+				self.common.plot_expression(Circles, false, "keyentry", &wedge_options[..]);
+			}
+
+			if percent_labels && total > 0.0
+			{
+				let r = explode + 0.5 * (1.0 + hole);
+				let text = format!("{:.1}%", 100.0 * v / total);
+				self.common.set_label_common(Label(Axis(r * mid.cos()), Axis(r * mid.sin())), &text[..], &[TextAlign(AlignCenter)]);
+			}
+			angle += sweep;
+		}
+
+		let extent = 1.05 + max_explode;
+		writeln!(&mut self.common.commands, "set size ratio -1");
+		writeln!(&mut self.common.commands, "unset border");
+		self.common.x_axis.set_range(Fix(-extent), Fix(extent));
+		self.common.y_axis.set_range(Fix(-extent), Fix(extent));
+		self.common.x_axis.set_ticks(None, &[], &[]);
+		self.common.y_axis.set_ticks(None, &[], &[]);
+		self
+	}
+
 	/// Draws an image from a rectangular array of data by connecting the individual datapoints with polygons.
 	///
 	/// #Arguments:
@@ -449,6 +1341,292 @@ impl Axes2D
 		self.common.plot_matrix(Image, false, mat, num_rows, num_cols, dimensions, options);
 		self
 	}
+
+	/// Draws contours (lines of equal value) of a rectangular array of data at specific levels. The contours are computed in Rust
+	/// using the marching squares algorithm, and are drawn as lines.
+	///
+	/// #Arguments:
+	/// * `mat` - Row-major 2D array signifying the value of the datapoints. The X and Y coordinates of the datapoints are determined automatically,
+	///           and optionally scaled using the `dimensions` argument.
+	/// * `num_rows` - Number of rows in the data array
+	/// * `num_cols` - Number of columns in the data array
+	/// * `dimensions` - Optional X and Y coordinates of the first and last data points (with the rest of the coordinates spaced evenly between).
+	///                  By default this will be `(0, 0)` and `(num_rows - 1, num_cols - 1)`.
+	/// * `levels` - Iterator for a set of levels
+	/// * `labels` - Whether to label each contour line with its level. The labels are formatted like the tick labels of the color bar
+	///              (see `set_cb_tick_format`).
+	/// * `filled` - Whether to fill the bands between successive levels with the palette color of the middle of the band. This also fixes the
+	///              range of the color bar to span the levels. Regions below the lowest level and above the highest level are not filled.
+	/// * `options` - Array of PlotOption controlling the appearance of the contour lines. Relevant options are:
+	///     * `Caption` - Specifies the caption for the contour lines. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the lines
+	///     * `LineStyle` - Sets the style of the lines
+	///     * `Color` - Sets the color of the lines
+	pub fn contour<'l,
+	               T: DataType,
+	               X: Iterator<Item = T>,
+	               Tl: DataType,
+	               L: Iterator<Item = Tl>>(&'l mut self, mat: X, num_rows: usize, num_cols: usize, dimensions: Option<(f64, f64, f64, f64)>,
+	                                       levels: L, labels: bool, filled: bool, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let mut mat: Vec<f64> = mat.map(|v| v.get()).take(num_rows * num_cols).collect();
+		let missing = num_rows * num_cols - mat.len();
+		mat.extend(repeat(f64::NAN).take(missing));
+		let mut levels: Vec<f64> = levels.map(|l| l.get()).filter(|l| l.is_finite()).collect();
+		levels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let dims = dimensions.unwrap_or((0.0, 0.0, (num_cols as f64 - 1.0).max(0.0), (num_rows as f64 - 1.0).max(0.0)));
+
+		if filled && levels.len() > 1
+		{
+			self.common.cb_axis.set_range(Fix(levels[0]), Fix(levels[levels.len() - 1]));
+			for band in levels.windows(2)
+			{
+				let polygons = compute_band_polygons(&mat[..], num_rows, num_cols, dims, band[0], band[1]);
+				if polygons.is_empty()
+				{
+					continue;
+				}
+				// The polygons are separated by NaN points, which gnuplot fills separately
+				let mut xs = vec![];
+				let mut ys = vec![];
+				for poly in polygons.iter()
+				{
+					for &(x, y) in poly.iter().chain(poly.first())
+					{
+						xs.push(x);
+						ys.push(y);
+					}
+					xs.push(f64::NAN);
+					ys.push(f64::NAN);
+				}
+				self.common.plot2(FillBetween, xs.iter(), ys.iter(), &[PaletteColor(0.5 * (band[0] + band[1]))]);
+			}
+		}
+
+		let lines = compute_contour_lines(&mat[..], num_rows, num_cols, dims, &levels[..]);
+		let mut xs = vec![];
+		let mut ys = vec![];
+		for line in lines.iter()
+		{
+			for &(x, y) in line.points.iter()
+			{
+				xs.push(x);
+				ys.push(y);
+			}
+			xs.push(f64::NAN);
+			ys.push(f64::NAN);
+
+			if labels
+			{
+				let (x, y) = line.midpoint();
+				self.contour_labels.push((x, y, line.level));
+			}
+		}
+		self.common.plot2(Lines, xs.iter(), ys.iter(), options);
+		self
+	}
+
+	/// Draws an image from a rectangular array of data (e.g. a confusion matrix or a correlation table), with each cell labeled with its value.
+	/// The first row is drawn at the top, and the rows and columns are labeled using the tick labels. The color of the value labels is picked to contrast
+	/// with the cell color, assuming a palette that goes from dark to light colors (like the default palette). The range of the color bar (see `set_cb_range`)
+	/// is taken into account no matter whether it is set before or after calling this method.
+	///
+	/// #Arguments:
+	/// * `mat` - Row-major 2D array signifying the value of the cells
+	/// * `num_rows` - Number of rows in the data array
+	/// * `num_cols` - Number of columns in the data array
+	/// * `row_labels` - Labels of the rows, starting with the top one. There can't be more labels than rows
+	/// * `col_labels` - Labels of the columns. There can't be more labels than columns
+	/// * `fmt` - Function that formats the value of a cell into its label
+	/// * `symmetric` - Whether to make the color bar range symmetric around 0, so that 0 falls at the center of the palette
+	/// * `options` - Array of PlotOption controlling the appearance of the image. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	pub fn annotated_heatmap<'l,
+	                         T: DataType,
+	                         X: Iterator<Item = T>,
+	                         F: Fn(f64) -> String>(&'l mut self, mat: X, num_rows: usize, num_cols: usize, row_labels: &[&str], col_labels: &[&str],
+	                                               fmt: F, symmetric: bool, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		assert!(row_labels.len() <= num_rows, "Got {} row labels for {} rows", row_labels.len(), num_rows);
+		assert!(col_labels.len() <= num_cols, "Got {} column labels for {} columns", col_labels.len(), num_cols);
+		let mut mat: Vec<f64> = mat.map(|v| v.get()).take(num_rows * num_cols).collect();
+		let missing = num_rows * num_cols - mat.len();
+		mat.extend(repeat(f64::NAN).take(missing));
+
+		let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+		for &v in mat.iter().filter(|v| v.is_finite())
+		{
+			lo = lo.min(v);
+			hi = hi.max(v);
+		}
+		if symmetric && lo <= hi
+		{
+			hi = lo.abs().max(hi.abs());
+			lo = -hi;
+			self.common.cb_axis.set_range(Fix(lo), Fix(hi));
+		}
+
+		for i in 0..num_rows
+		{
+			for j in 0..num_cols
+			{
+				let v = mat[i * num_cols + j];
+				if !v.is_finite()
+				{
+					continue;
+				}
+				let y = (num_rows - 1 - i) as f64;
+				self.heatmap_labels.push((j as f64, y, fmt(v), v));
+			}
+		}
+
+		let flipped = (0..num_rows).rev().flat_map(|i| mat[i * num_cols..(i + 1) * num_cols].to_vec());
+		self.common.plot_matrix(Image, false, flipped, num_rows, num_cols, None, options);
+
+		self.common.x_axis.set_ticks_custom(col_labels.iter().enumerate().map(|(j, l)| Major(j as f64, Fix(l.replace("%", "%%")))), &[], &[]);
+		self.common.y_axis.set_ticks_custom(row_labels.iter().enumerate().map(|(i, l)| Major((num_rows - 1 - i) as f64, Fix(l.replace("%", "%%")))), &[], &[]);
+		self.common.x_axis.set_range(Fix(-0.5), Fix(num_cols as f64 - 0.5));
+		self.common.y_axis.set_range(Fix(-0.5), Fix(num_rows as f64 - 0.5));
+		self
+	}
+
+	/// Draws an image from a rectangular array of data, where the rows and columns are placed at arbitrary (but increasing) coordinates,
+	/// e.g. for a spectrogram with logarithmically spaced frequencies. Each datapoint is drawn as a rectangular cell colored according to the palette.
+	///
+	/// #Arguments:
+	/// * `mat` - Row-major 2D array signifying the value of the datapoints
+	/// * `num_rows` - Number of rows in the data array
+	/// * `num_cols` - Number of columns in the data array
+	/// * `x_coords` - X coordinates of the columns. Either `num_cols + 1` cell edges, or `num_cols` cell centers (in which case the edges are placed halfway between them)
+	/// * `y_coords` - Y coordinates of the rows. Either `num_rows + 1` cell edges, or `num_rows` cell centers
+	/// * `options` - Array of PlotOption controlling the appearance of the image. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `FillAlpha` - Sets the transparency of the cells
+	pub fn image_nonuniform<'l,
+	                        T: DataType,
+	                        X: Iterator<Item = T>>(&'l mut self, mat: X, num_rows: usize, num_cols: usize, x_coords: &[f64], y_coords: &[f64],
+	                                               options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let x_edges = grid_edges(x_coords, num_cols);
+		let y_edges = grid_edges(y_coords, num_rows);
+
+		let mut mat = mat.map(|v| v.get());
+		let mut x = vec![];
+		let mut y = vec![];
+		let mut dx = vec![];
+		let mut dy = vec![];
+		let mut z = vec![];
+		for row in y_edges.windows(2)
+		{
+			for col in x_edges.windows(2)
+			{
+				x.push(0.5 * (col[0] + col[1]));
+				y.push(0.5 * (row[0] + row[1]));
+				dx.push(0.5 * (col[1] - col[0]));
+				dy.push(0.5 * (row[1] - row[0]));
+				z.push(mat.next().unwrap_or(f64::NAN));
+			}
+		}
+		self.common.plot5(PaletteBoxXYError, x.iter(), y.iter(), dx.iter(), dy.iter(), z.iter(), options);
+		self
+	}
+
+	/// Draws an image from a rectangular array of colors, specified as red, green and blue components ranging from 0 to 255.
+	///
+	/// #Arguments:
+	/// * `mat` - Row-major 2D array of the pixel colors, with the three components of each pixel stored next to each other (i.e. `r, g, b, r, g, b, ...`).
+	///           The first row is placed at the bottom of the image.
+	/// * `num_rows` - Number of rows in the image
+	/// * `num_cols` - Number of columns in the image
+	/// * `dimensions` - Optional X and Y coordinates of the first and last pixels (with the rest of the coordinates spaced evenly between).
+	///                  By default this will be `(0, 0)` and `(num_rows - 1, num_cols - 1)`.
+	/// * `options` - Array of PlotOption controlling the appearance of the image. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	pub fn rgb_image<'l,
+	                 T: DataType,
+	                 X: Iterator<Item = T>>(&'l mut self, mat: X, num_rows: usize, num_cols: usize, dimensions: Option<(f64, f64, f64, f64)>,
+	                                        options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot_matrix(RGBImage, false, mat, num_rows, num_cols, dimensions, options);
+		self
+	}
+
+	/// Like `rgb_image`, but each pixel has an additional alpha (opacity) component, ranging from 0 (fully transparent) to 255 (fully opaque).
+	/// The components of each pixel are stored next to each other (i.e. `r, g, b, a, r, g, b, a, ...`).
+	pub fn rgba_image<'l,
+	                  T: DataType,
+	                  X: Iterator<Item = T>>(&'l mut self, mat: X, num_rows: usize, num_cols: usize, dimensions: Option<(f64, f64, f64, f64)>,
+	                                         options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		self.common.plot_matrix(RGBAlphaImage, false, mat, num_rows, num_cols, dimensions, options);
+		self
+	}
+
+	/// Like `rgb_image`, but the red, green and blue components are specified as separate row-major 2D arrays.
+	pub fn rgb_image_planes<'l,
+	                        Tr: DataType, R: Iterator<Item = Tr>,
+	                        Tg: DataType, G: Iterator<Item = Tg>,
+	                        Tb: DataType, B: Iterator<Item = Tb>>(&'l mut self, r: R, g: G, b: B, num_rows: usize, num_cols: usize,
+	                                                              dimensions: Option<(f64, f64, f64, f64)>, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let mut mat = Vec::with_capacity(3 * num_rows * num_cols);
+		for ((r, g), b) in r.zip(g).zip(b)
+		{
+			mat.push(r.get());
+			mat.push(g.get());
+			mat.push(b.get());
+		}
+		self.common.plot_matrix(RGBImage, false, mat.iter(), num_rows, num_cols, dimensions, options);
+		self
+	}
+
+	/// Like `rgba_image`, but the red, green, blue and alpha components are specified as separate row-major 2D arrays.
+	pub fn rgba_image_planes<'l,
+	                         Tr: DataType, R: Iterator<Item = Tr>,
+	                         Tg: DataType, G: Iterator<Item = Tg>,
+	                         Tb: DataType, B: Iterator<Item = Tb>,
+	                         Ta: DataType, A: Iterator<Item = Ta>>(&'l mut self, r: R, g: G, b: B, a: A, num_rows: usize, num_cols: usize,
+	                                                               dimensions: Option<(f64, f64, f64, f64)>, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let mut mat = Vec::with_capacity(4 * num_rows * num_cols);
+		for (((r, g), b), a) in r.zip(g).zip(b).zip(a)
+		{
+			mat.push(r.get());
+			mat.push(g.get());
+			mat.push(b.get());
+			mat.push(a.get());
+		}
+		self.common.plot_matrix(RGBAlphaImage, false, mat.iter(), num_rows, num_cols, dimensions, options);
+		self
+	}
+
+	/// Like `rgb_image`, but takes the pixels from an `RgbImage` of the `image` crate. The image is drawn upright, i.e. the first row
+	/// of the image is placed at the top. Requires the `image` feature.
+	///
+	/// #Arguments:
+	/// * `img` - The image
+	/// * `dimensions` - Optional X and Y coordinates of the bottom left and top right pixels (with the rest of the coordinates spaced evenly between).
+	///                  By default this will be `(0, 0)` and `(width - 1, height - 1)`.
+	/// * `options` - Array of PlotOption controlling the appearance of the image. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	#[cfg(feature = "image")]
+	pub fn rgb_image_buffer<'l>(&'l mut self, img: &::image::RgbImage, dimensions: Option<(f64, f64, f64, f64)>, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let (w, h) = img.dimensions();
+		let mat = (0..h).rev().flat_map(|y| (0..w).flat_map(move |x| img.get_pixel(x, y).0.to_vec()));
+		self.common.plot_matrix(RGBImage, false, mat, h as usize, w as usize, dimensions, options);
+		self
+	}
+
+	/// Like `rgb_image_buffer`, but takes the pixels from an `RgbaImage` of the `image` crate. Requires the `image` feature.
+	#[cfg(feature = "image")]
+	pub fn rgba_image_buffer<'l>(&'l mut self, img: &::image::RgbaImage, dimensions: Option<(f64, f64, f64, f64)>, options: &[PlotOption]) -> &'l mut Axes2D
+	{
+		let (w, h) = img.dimensions();
+		let mat = (0..h).rev().flat_map(|y| (0..w).flat_map(move |x| img.get_pixel(x, y).0.to_vec()));
+		self.common.plot_matrix(RGBAlphaImage, false, mat, h as usize, w as usize, dimensions, options);
+		self
+	}
 }
 
 impl AxesCommonPrivate for Axes2D
@@ -462,15 +1640,60 @@ impl AxesCommonPrivate for Axes2D
 	{
 		&self.common
 	}
+
+	fn get_axis_mut<'l>(&'l mut self, axis: TickAxis) -> Option<&'l mut AxisData>
+	{
+		match axis
+		{
+			X2TickAxis => Some(&mut self.x2_axis),
+			Y2TickAxis => Some(&mut self.y2_axis),
+			RTickAxis => Some(&mut self.r_axis),
+			// The angular axis always spans the full circle
+			TTickAxis => None,
+			axis => self.common.get_axis_mut(axis)
+		}
+	}
 }
 
 impl AxesCommon for Axes2D {}
 
+fn line_color<'l>(options: &[PlotOption<'l>]) -> &'l str
+{
+	let mut color = "black";
+	first_opt!{options,
+		Color(s) =>
+		{
+			color = s;
+		}
+	}
+	color
+}
+
+fn has_caption(options: &[PlotOption]) -> bool
+{
+	let mut ret = false;
+	first_opt!{options,
+		Caption(s) =>
+		{
+			ret = !s.is_empty();
+		}
+	}
+	ret
+}
+
 pub fn new_axes2d() -> Axes2D
 {
 	Axes2D
 	{
 		common: AxesCommonData::new(),
+		polar: None,
+		polar_grid: None,
+		x2_axis: AxisData::new(X2TickAxis),
+		y2_axis: AxisData::new(Y2TickAxis),
+		r_axis: AxisData::new(RTickAxis),
+		theta_axis: AxisData::new(TTickAxis),
+		heatmap_labels: vec![],
+		contour_labels: vec![],
 	}
 }
 
@@ -480,6 +1703,38 @@ pub trait Axes2DPrivate
 	fn write_out(&self, writer: &mut Writer);
 }
 
+impl Axes2D
+{
+	fn write_heatmap_labels(&self, writer: &mut Writer)
+	{
+		let (data_lo, data_hi) = self.heatmap_labels.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, _, _, v)| (lo.min(v), hi.max(v)));
+		let lo = match self.common.cb_axis.min { Fix(v) => v, Auto => data_lo };
+		let hi = match self.common.cb_axis.max { Fix(v) => v, Auto => data_hi };
+
+		let mut c = vec![];
+		for &(x, y, ref text, v) in self.heatmap_labels.iter()
+		{
+			// Light text on the dark end of the palette, dark text on the light end
+			let frac = if hi != lo { (v - lo) / (hi - lo) } else { 0.5 };
+			let color = if frac > 0.5 { "black" } else { "white" };
+			write_label(&mut c, Label(Axis(x), Axis(y)), &text[..], &[TextAlign(AlignCenter), TextColor(color)]);
+		}
+		writer.write_all(&c[..]);
+	}
+
+	fn write_contour_labels(&self, writer: &mut Writer)
+	{
+		// gnuplot's default tick format is "% h"
+		let fmt = self.common.cb_axis.format.as_ref().map_or("% h", |f| &f[..]);
+		let mut c = vec![];
+		for &(x, y, level) in self.contour_labels.iter()
+		{
+			write_label_expr(&mut c, Label(Axis(x), Axis(y)), &format!("gprintf(\"{}\", {:.17e})", escape(fmt), level)[..], &[TextAlign(AlignCenter)]);
+		}
+		writer.write_all(&c[..]);
+	}
+}
+
 impl Axes2DPrivate for Axes2D
 {
 	fn write_out(&self, writer: &mut Writer)
@@ -490,6 +1745,89 @@ impl Axes2DPrivate for Axes2D
 		}
 
 		self.common.write_out_commands(writer);
-		self.common.write_out_elements("plot", writer);
+		self.x2_axis.write_out_commands(writer, self.common.data_range(X2TickAxis));
+		self.y2_axis.write_out_commands(writer, self.common.data_range(Y2TickAxis));
+		self.write_heatmap_labels(writer);
+		self.write_contour_labels(writer);
+
+		match self.polar
+		{
+			Some((origin, clockwise)) =>
+			{
+				writeln!(writer, "set polar");
+				writeln!(writer, "set angles degrees");
+				writeln!(writer, "set theta {} {}", match origin
+				{
+					ThetaRight => "right",
+					ThetaTop => "top",
+					ThetaLeft => "left",
+					ThetaBottom => "bottom",
+				},
+				if clockwise
+				{
+					"clockwise"
+				}
+				else
+				{
+					"counterclockwise"
+				});
+				// In polar mode the second data column holds the radius
+				self.r_axis.write_out_commands(writer, self.common.data_range(YTickAxis));
+				writer.write_all(&self.theta_axis.ticks_buf[..]);
+
+				match self.polar_grid
+				{
+					Some((angle, ref style)) =>
+					{
+						writer.write_str("set grid polar");
+						match angle
+						{
+							Fix(a) =>
+							{
+								write!(writer, " {:.12e}", a);
+							},
+							Auto => ()
+						}
+						if !self.common.has_grid
+						{
+							writer.write_all(&style[..]);
+						}
+						writer.write_str("\n");
+					},
+					None => ()
+				}
+			},
+			None => ()
+		}
+
+		self.common.write_out_elements("plot", Some(&self.x2_axis), writer);
+	}
+}
+
+#[cfg(test)]
+mod test
+{
+	use super::*;
+
+	#[test]
+	fn annotated_heatmap_labels()
+	{
+		let mut axes = new_axes2d();
+		axes.annotated_heatmap([1.0f64, 2.0, 3.0, 4.0].iter(), 2, 2, &["top", "bottom"], &["left"], |v| format!("{}", v), false, &[]);
+		assert_eq!(axes.heatmap_labels.len(), 4);
+	}
+
+	#[test]
+	#[should_panic(expected = "Got 3 row labels for 2 rows")]
+	fn annotated_heatmap_too_many_row_labels()
+	{
+		new_axes2d().annotated_heatmap([1.0f64, 2.0, 3.0, 4.0].iter(), 2, 2, &["a", "b", "c"], &[], |v| format!("{}", v), false, &[]);
+	}
+
+	#[test]
+	#[should_panic(expected = "Got 3 column labels for 2 columns")]
+	fn annotated_heatmap_too_many_col_labels()
+	{
+		new_axes2d().annotated_heatmap([1.0f64, 2.0, 3.0, 4.0].iter(), 2, 2, &[], &["a", "b", "c"], |v| format!("{}", v), false, &[]);
 	}
 }
@@ -7,6 +7,7 @@ use std::io::Write;
 use axes_common::*;
 use datatype::*;
 use options::*;
+use sampling::*;
 use writer::Writer;
 
 /// 3D axes that is used for drawing 3D plots
@@ -41,6 +42,77 @@ impl Axes3D
 		self
 	}
 
+	/// Like `surface`, but the rows and columns of the data array are placed at arbitrary (but increasing) coordinates,
+	/// e.g. logarithmically spaced frequencies.
+	///
+	/// #Arguments:
+	/// * `mat` - Row-major 2D array signifying the Z coordinate of the datapoints
+	/// * `num_rows` - Number of rows in the data array
+	/// * `num_cols` - Number of columns in the data array
+	/// * `x_coords` - X coordinates of the columns. Either `num_cols` datapoint coordinates, or `num_cols + 1` cell edges (in which case the datapoints
+	///                are placed halfway between them)
+	/// * `y_coords` - Y coordinates of the rows. Either `num_rows` datapoint coordinates, or `num_rows + 1` cell edges
+	/// * `options` - Array of PlotOption controlling the appearance of the surface. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	pub fn surface_nonuniform<'l, T: DataType, X: Iterator<Item = T>>(&'l mut self, mat: X, num_rows: usize, num_cols: usize, x_coords: &[f64], y_coords: &[f64],
+	                                                                options: &[PlotOption]) -> &'l mut Axes3D
+	{
+		let x_centers = grid_centers(x_coords, num_cols);
+		let y_centers = grid_centers(y_coords, num_rows);
+		self.common.plot_grid(Pm3D, true, mat, num_rows, num_cols, &x_centers[..], &y_centers[..], options);
+		self
+	}
+
+	/// Draws a 3D surface from a gnuplot expression of `x` and `y` (e.g. `"sin(x) * cos(y)"`). The expression is evaluated by gnuplot itself,
+	/// over the ranges of the X and Y axes. The density of the evaluation grid is controlled by `set_samples` and `set_isosamples`.
+	///
+	/// #Arguments:
+	/// * `expr` - The expression to plot, in gnuplot syntax
+	/// * `options` - Array of PlotOption controlling the appearance of the surface. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	pub fn expression_surface<'l>(&'l mut self, expr: &str, options: &[PlotOption]) -> &'l mut Axes3D
+	{
+		self.common.plot_expression(Pm3D, true, expr, options);
+		self
+	}
+
+	/// Sets the density of the grid used to evaluate the expressions passed to `expression_surface`.
+	///
+	/// #Arguments:
+	/// * `x_samples` - Number of grid lines along the X axis
+	/// * `y_samples` - Number of grid lines along the Y axis
+	pub fn set_isosamples<'l>(&'l mut self, x_samples: u32, y_samples: u32) -> &'l mut Axes3D
+	{
+		assert!(x_samples > 1 && y_samples > 1, "Need at least 2 isosamples");
+		writeln!(&mut self.common.commands, "set isosamples {},{}", x_samples, y_samples);
+		self
+	}
+
+	/// Plot a 3D parametric curve, specified by a function of a parameter `t`, as a line. The function is first evaluated at evenly spaced values
+	/// of `t`, after which additional samples are added where the curve bends sharply, so that the curve looks smooth without having to pick
+	/// the number of samples by hand. Non-finite values of the function create gaps in the line.
+	///
+	/// #Arguments:
+	/// * `t_range` - The first and last values of `t`
+	/// * `samples` - Number of initial evenly spaced samples
+	/// * `f` - Function that maps `t` to the x, y and z coordinates of the curve
+	/// * `options` - Array of PlotOption controlling the appearance of the curve. Relevant options are:
+	///     * `Caption` - Specifies the caption for this dataset. Use an empty string to hide it (default).
+	///     * `LineWidth` - Sets the width of the line
+	///     * `LineStyle` - Sets the style of the line
+	///     * `Color` - Sets the color
+	pub fn parametric_curve<'l, F: FnMut(f64) -> (f64, f64, f64)>(&'l mut self, t_range: (f64, f64), samples: usize, mut f: F, options: &[PlotOption]) -> &'l mut Axes3D
+	{
+		let (t_min, t_max) = t_range;
+		let points = sample_curve(t_min, t_max, samples, |t|
+		{
+			let (x, y, z) = f(t);
+			[x, y, z]
+		});
+		self.common.plot3(Lines, points.iter().map(|p| p[0]), points.iter().map(|p| p[1]), points.iter().map(|p| p[2]), options);
+		self
+	}
+
 	/// Sets the 3D view.
 	///
 	/// #Arguments:
@@ -89,6 +161,21 @@ impl Axes3D
 		self
 	}
 
+	/// Like `set_x_tick_format` but for the Z axis.
+	pub fn set_z_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Axes3D
+	{
+		self.z_axis.set_format(format);
+		self
+	}
+
+	/// Like `set_x_tick_formatter` but for the Z axis. The range of the plotted data is not tracked for this axis,
+	/// so the ticks cover the range set by `set_z_range`, falling back to -10 to 10 for the ends that are not fixed.
+	pub fn set_z_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Axes3D
+	{
+		self.z_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
 	/// Set the range of values for the Z axis
 	///
 	/// # Arguments
@@ -180,6 +267,15 @@ impl AxesCommonPrivate for Axes3D
 	{
 		&self.common
 	}
+
+	fn get_axis_mut<'l>(&'l mut self, axis: TickAxis) -> Option<&'l mut AxisData>
+	{
+		match axis
+		{
+			ZTickAxis => Some(&mut self.z_axis),
+			axis => self.common.get_axis_mut(axis)
+		}
+	}
 }
 
 impl AxesCommon for Axes3D {}
@@ -318,7 +414,7 @@ impl Axes3DPrivate for Axes3D
 		}
 
 		self.common.write_out_commands(w);
-		self.z_axis.write_out_commands(w);
-		self.common.write_out_elements("splot", w);
+		self.z_axis.write_out_commands(w, EMPTY_RANGE);
+		self.common.write_out_elements("splot", None, w);
 	}
 }
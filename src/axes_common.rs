@@ -3,10 +3,15 @@
 // All rights reserved. Distributed under LGPL 3.0. For full terms see the file LICENSE.
 
 use std::io::Write;
+use std::iter::repeat;
+use std::f64;
 
 use datatype::*;
 use coordinates::*;
 use options::*;
+use query::{Stats, StatsXY};
+use sampling::*;
+use ticks::*;
 use writer::*;
 
 pub use self::LabelType::*;
@@ -25,7 +30,9 @@ impl PlotWriter for Vec<u8>
 pub struct PlotElement
 {
 	pub args: Vec<u8>,
-	pub data: Vec<u8>
+	pub data: Vec<u8>,
+	/// If set, the data of this element is sampled from this function when the plot is written out
+	pub function: Option<FunctionSource>,
 }
 
 impl PlotElement
@@ -36,6 +43,64 @@ impl PlotElement
 		{
 			args: vec![],
 			data: vec![],
+			function: None,
+		}
+	}
+}
+
+/// A function of x that is sampled once the range of the X axis it is plotted against is known
+pub struct FunctionSource
+{
+	pub f: Box<Fn(f64) -> f64>,
+	pub x_range: (AutoOption<f64>, AutoOption<f64>),
+	pub samples: usize,
+	pub x_axis: XAxis,
+}
+
+impl FunctionSource
+{
+	/// Samples the function over its range, resolving `Auto` ends from the range of `axis` and the `data_range` plotted against it.
+	/// Panics if the axis is logarithmic and the range is not positive, as there would be nothing to plot.
+	pub fn sample(&self, axis: &AxisData, data_range: (f64, f64)) -> Vec<[f64; 3]>
+	{
+		let log = axis.log_base.is_some();
+		let (default_min, default_max) = if log
+		{
+			(1.0, 10.0)
+		}
+		else
+		{
+			(-10.0, 10.0)
+		};
+		let (data_min, data_max) = if data_range.0 <= data_range.1
+		{
+			data_range
+		}
+		else
+		{
+			(default_min, default_max)
+		};
+		let pick = |v: AutoOption<f64>, axis_v: AutoOption<f64>, data_v: f64, default: f64| match (v, axis_v)
+		{
+			(Fix(v), _) | (Auto, Fix(v)) => v,
+			(Auto, Auto) => if log && data_v <= 0.0 { default } else { data_v },
+		};
+		let x_min = pick(self.x_range.0, axis.min, data_min, default_min);
+		let x_max = pick(self.x_range.1, axis.max, data_max, default_max);
+
+		if log
+		{
+			assert!(x_min > 0.0 && x_max > 0.0, "The range of a function on a logarithmic axis must be positive, but it is [{}:{}]", x_min, x_max);
+			let mut points = sample_curve(x_min.ln(), x_max.ln(), self.samples, |u| [u, (self.f)(u.exp()), 0.0]);
+			for p in points.iter_mut()
+			{
+				p[0] = p[0].exp();
+			}
+			points
+		}
+		else
+		{
+			sample_curve(x_min, x_max, self.samples, |x| [x, (self.f)(x), 0.0])
 		}
 	}
 }
@@ -45,6 +110,8 @@ pub enum LabelType
 {
 	XLabel,
 	YLabel,
+	X2Label,
+	Y2Label,
 	ZLabel,
 	CBLabel,
 	TitleLabel,
@@ -132,27 +199,48 @@ pub fn write_out_label_options<T: PlotWriter + Writer>(label_type: LabelType, op
 				}
 			}
 		}
+	}
 
-		first_opt!{options,
-			TextAlign(a) =>
-			{
-				write!(w, "{}", match a
+	match label_type
+	{
+		Label(..) | AxesTicks =>
+		{
+			first_opt!{options,
+				TextAlign(a) =>
 				{
-					AlignLeft => " left",
-					AlignRight => " right",
-					_ => " center",
-				});
+					write!(w, "{}", match a
+					{
+						AlignLeft => " left",
+						AlignRight => " right",
+						_ => " center",
+					});
+				}
 			}
-		}
+		},
+		_ => ()
 	}
 }
 
+/// An axis that has ticks
+#[derive(Copy, Clone)]
 pub enum TickAxis
 {
+	/// The X axis
 	XTickAxis,
+	/// The Y axis
 	YTickAxis,
+	/// The secondary (top) X axis
+	X2TickAxis,
+	/// The secondary (right) Y axis
+	Y2TickAxis,
+	/// The Z axis
 	ZTickAxis,
+	/// The color bar axis
 	CBTickAxis,
+	/// The radial axis of polar axes
+	RTickAxis,
+	/// The angular axis of polar axes
+	TTickAxis,
 }
 
 impl TickAxis
@@ -163,8 +251,12 @@ impl TickAxis
 		{
 			XTickAxis => "x",
 			YTickAxis => "y",
+			X2TickAxis => "x2",
+			Y2TickAxis => "y2",
 			ZTickAxis => "z",
 			CBTickAxis => "cb",
+			RTickAxis => "r",
+			TTickAxis => "t",
 		}
 	}
 
@@ -174,19 +266,29 @@ impl TickAxis
 		{
 			XTickAxis => "xtics",
 			YTickAxis => "ytics",
+			X2TickAxis => "x2tics",
+			Y2TickAxis => "y2tics",
 			ZTickAxis => "ztics",
 			CBTickAxis => "cbtics",
+			RTickAxis => "rtics",
+			TTickAxis => "ttics",
 		}
 	}
 
+	/// The angular axis of polar axes always spans the full circle, so it only has ticks and no range
 	pub fn to_range_str(&self) -> &str
 	{
 		match *self
 		{
 			XTickAxis => "xrange",
 			YTickAxis => "yrange",
+			X2TickAxis => "x2range",
+			Y2TickAxis => "y2range",
 			ZTickAxis => "zrange",
 			CBTickAxis => "cbrange",
+			RTickAxis => "rrange",
+			// "trange" is the range of the parametric variable, not of the angle
+			TTickAxis => panic!("The angular axis has no range"),
 		}
 	}
 }
@@ -202,6 +304,18 @@ pub enum PlotType
 	Boxes,
 	Pm3D,
 	Image,
+	Steps,
+	FSteps,
+	HiSteps,
+	Impulses,
+	PalettePoints,
+	RGBPoints,
+	BoxXYError,
+	Circles,
+	Ellipses,
+	RGBImage,
+	RGBAlphaImage,
+	PaletteBoxXYError,
 }
 
 impl PlotType
@@ -214,7 +328,14 @@ impl PlotType
 			LinesPoints |
 			XErrorLines |
 			Boxes |
-			YErrorLines => true,
+			YErrorLines |
+			Steps |
+			FSteps |
+			HiSteps |
+			Impulses |
+			BoxXYError |
+			Circles |
+			Ellipses => true,
 			_ => false
 		}
 	}
@@ -226,7 +347,29 @@ impl PlotType
 			Points |
 			LinesPoints |
 			XErrorLines |
-			YErrorLines => true,
+			YErrorLines |
+			PalettePoints |
+			RGBPoints => true,
+			_ => false
+		}
+	}
+
+	fn num_channels(&self) -> usize
+	{
+		match *self
+		{
+			RGBImage => 3,
+			RGBAlphaImage => 4,
+			_ => 1
+		}
+	}
+
+	fn is_variable_color(&self) -> bool
+	{
+		match *self
+		{
+			PalettePoints |
+			RGBPoints => true,
 			_ => false
 		}
 	}
@@ -236,20 +379,77 @@ impl PlotType
 		match *self
 		{
 			Boxes |
+			BoxXYError |
+			Circles |
+			Ellipses |
+			PaletteBoxXYError |
 			FillBetween => true,
 			_ => false
 		}
 	}
 }
 
+/// Writes `set <axis>tics (...)` listing the ticks, without the trailing tick options
+fn write_custom_ticks<T: DataType, TL: Iterator<Item = Tick<T>>>(c: &mut Vec<u8>, axis: TickAxis, ticks: TL)
+{
+	c.write_str("set ");
+	c.write_str(axis.to_tick_str());
+	c.write_str(" (");
+
+	let mut first = true;
+	for tick in ticks
+	{
+		if first
+		{
+			first = false;
+		}
+		else
+		{
+			c.write_str(",");
+		}
+
+		let a = Auto;
+		let (ref pos, ref label, level) = match tick
+		{
+			Minor(ref pos) =>
+			{
+				(pos, &a, 1)
+			},
+			Major(ref pos, ref label) =>
+			{
+				(pos, label, 0)
+			}
+		};
+
+		match **label
+		{
+			Fix(ref label) =>
+			{
+				c.write_str("\"");
+				c.write_str(&label[..]);
+				c.write_str("\" ");
+			},
+			Auto => ()
+		}
+		write!(&mut *c, "{:.12e} {}", pos.get(), level);
+	}
+	c.write_str(")");
+}
+
 pub struct AxisData
 {
 	pub ticks_buf: Vec<u8>,
+	/// The gnuplot format of the tick labels, if set
+	pub format: Option<String>,
 	pub log_base: Option<f64>,
 	pub mticks: i32,
 	pub axis: TickAxis,
 	pub min: AutoOption<f64>,
 	pub max: AutoOption<f64>,
+	pub formatter: Option<Box<Fn(f64) -> String>>,
+	pub reverse: bool,
+	pub fix_min: bool,
+	pub fix_max: bool,
 }
 
 impl AxisData
@@ -259,15 +459,21 @@ impl AxisData
 		AxisData
 		{
 			ticks_buf: vec![],
+			format: None,
 			log_base: None,
 			mticks: 0,
 			axis: axis,
 			min: Auto,
 			max: Auto,
+			formatter: None,
+			reverse: false,
+			fix_min: false,
+			fix_max: false,
 		}
 	}
 	
-	pub fn write_out_commands(&self, w: &mut Writer)
+	/// `data_range` is the minimum and maximum of the data plotted against this axis, which is used to place the ticks of the formatter
+	pub fn write_out_commands(&self, w: &mut Writer, data_range: (f64, f64))
 	{
 		let log = match self.log_base
 		{
@@ -308,77 +514,93 @@ impl AxisData
 		w.write_str("set ");
 		w.write_str(self.axis.to_range_str());
 		w.write_str(" [");
-		match self.min
+		// Fully fixed ranges are reversed by swapping the ends, as gnuplot only applies the reverse keyword to autoscaled axes
+		let swap = match (self.reverse, self.min, self.max)
+		{
+			(true, Fix(_), Fix(_)) => true,
+			_ => false
+		};
+		let (min, max) = if swap { (self.max, self.min) } else { (self.min, self.max) };
+		match min
 		{
 			Fix(v) => write!(w, "{:.12e}", v),
 			Auto => w.write_str("*")
 		};
 		w.write_str(":");
-		match self.max
+		match max
 		{
 			Fix(v) => write!(w, "{:.12e}", v),
 			Auto => w.write_str("*")
 		};
-		w.write_str("]\n");
+		w.write_str("]");
+		if self.reverse && !swap
+		{
+			w.write_str(" reverse");
+		}
+		w.write_str("\n");
+		if self.fix_min
+		{
+			writeln!(w, "set autoscale {}fixmin", self.axis.to_axis_str());
+		}
+		if self.fix_max
+		{
+			writeln!(w, "set autoscale {}fixmax", self.axis.to_axis_str());
+		}
 		
-		w.write_all(&self.ticks_buf[..]);
-	}
-	
-	pub fn set_ticks_custom<T: DataType, TL: Iterator<Item = Tick<T>>>(&mut self, ticks: TL, tick_options: &[TickOption], label_options: &[LabelOption])
-	{
-		// Set to 0 so that we don't get any non-custom ticks
-		self.mticks = 0;
+		match self.formatter
 		{
-			let c = &mut self.ticks_buf;
-			c.truncate(0);
-
-			c.write_str("set ");
-			c.write_str(self.axis.to_tick_str());
-			c.write_str(" (");
-
-			let mut first = true;
-			for tick in ticks
+			Some(ref f) =>
 			{
-				if first
+				let (data_min, data_max) = if data_range.0 <= data_range.1
 				{
-					first = false;
+					data_range
 				}
 				else
 				{
-					c.write_str(",");
-				}
-
-				let a = Auto;
-				let (ref pos, ref label, level) = match tick
-				{
-					Minor(ref pos) =>
-					{
-						(pos, &a, 1)
-					},
-					Major(ref pos, ref label) =>
-					{
-						(pos, label, 0)
-					}
+					// Only expressions were plotted, so assume gnuplot's default range
+					(-10.0, 10.0)
 				};
-
-				match **label
-				{
-					Fix(ref label) =>
-					{
-						c.write_str("\"");
-						c.write_str(&label[..]);
-						c.write_str("\" ");
-					},
-					Auto => ()
-				}
-				write!(&mut *c, "{:.12e} {}", pos.get(), level);
-			}
-			c.write_str(")");
+				let lo = match self.min { Fix(v) => v, Auto => data_min };
+				let hi = match self.max { Fix(v) => v, Auto => data_max };
+				let mut c = vec![];
+				// The labels are interpreted as format strings by gnuplot
+				write_custom_ticks(&mut c, self.axis, auto_ticks(lo, hi, self.log_base).iter().map(|&v| Major(v, Fix(f(v).replace("%", "%%")))));
+				w.write_all(&c[..]);
+			},
+			None => ()
+		}
+		w.write_all(&self.ticks_buf[..]);
+		match self.format
+		{
+			Some(ref fmt) =>
+			{
+				writeln!(w, "set format {} \"{}\"", self.axis.to_axis_str(), escape(fmt));
+			},
+			None => ()
 		}
+	}
+	
+	pub fn set_ticks_custom<T: DataType, TL: Iterator<Item = Tick<T>>>(&mut self, ticks: TL, tick_options: &[TickOption], label_options: &[LabelOption])
+	{
+		// Set to 0 so that we don't get any non-custom ticks
+		self.mticks = 0;
+		self.formatter = None;
+		self.ticks_buf.truncate(0);
+		write_custom_ticks(&mut self.ticks_buf, self.axis, ticks);
 		self.set_ticks_options(tick_options, label_options);
 		self.ticks_buf.write_str("\n");
 	}
 
+	pub fn set_formatter(&mut self, formatter: Box<Fn(f64) -> String>, tick_options: &[TickOption], label_options: &[LabelOption])
+	{
+		self.mticks = 0;
+		self.ticks_buf.truncate(0);
+		// The ticks themselves are only known once all the data has been added, so just store the options for now
+		self.set_ticks_options(tick_options, label_options);
+		self.ticks_buf.write_str("\n");
+		self.formatter = Some(formatter);
+	}
+
 	fn set_ticks_options(&mut self, tick_options: &[TickOption], label_options: &[LabelOption])
 	{
 		let c = &mut self.ticks_buf;
@@ -439,6 +661,7 @@ impl AxisData
 
 	pub fn set_ticks(&mut self, tick_placement: Option<(AutoOption<f64>, u32)>, tick_options: &[TickOption], label_options: &[LabelOption])
 	{
+		self.formatter = None;
 		self.ticks_buf.truncate(0);
 			
 		self.mticks = match tick_placement
@@ -480,12 +703,45 @@ impl AxisData
 		self.ticks_buf.write_str("\n");
 	}
 
+	pub fn set_format(&mut self, format: TickFormat)
+	{
+		let fmt = match format
+		{
+			Printf(f) => f.to_string(),
+			Scientific(p) => format!("%.{}t×10^{{%T}}", p),
+			SIPrefix(p) => format!("%.{}s%c", p),
+			Percent(p) => format!("%.{}f%%", p),
+		};
+		self.format = Some(fmt);
+	}
+
 	pub fn set_range(&mut self, min: AutoOption<f64>, max: AutoOption<f64>)
 	{
 		self.min = min;
 		self.max = max;
 	}
 
+	pub fn set_range_options(&mut self, options: &[RangeOption])
+	{
+		self.reverse = false;
+		self.fix_min = false;
+		self.fix_max = false;
+		for o in options.iter()
+		{
+			match *o
+			{
+				ReverseRange => self.reverse = true,
+				FixMin => self.fix_min = true,
+				FixMax => self.fix_max = true,
+				NoExtend =>
+				{
+					self.fix_min = true;
+					self.fix_max = true;
+				},
+			}
+		}
+	}
+
 	pub fn set_log(&mut self, base: Option<f64>)
 	{
 		self.log_base = base;
@@ -499,11 +755,21 @@ pub struct AxesCommonData
 	pub grid_rows: u32,
 	pub grid_cols: u32,
 	pub grid_pos: Option<u32>,
+	/// Whether `AxesCommon::set_grid` has turned on any grid lines
+	pub has_grid: bool,
 	pub x_axis: AxisData,
 	pub y_axis: AxisData,
 	pub cb_axis: AxisData,
+	/// Minimum and maximum of the data plotted against the X, Y, X2 and Y2 axes
+	pub data_ranges: [(f64, f64); 4],
 }
 
+/// The range of an axis that has no data plotted against it
+pub const EMPTY_RANGE: (f64, f64) = (f64::INFINITY, f64::NEG_INFINITY);
+
+/// Colors assigned to successive series of multi-series plots (e.g. bar charts)
+pub const SERIES_COLORS: [&'static str; 8] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f"];
+
 pub fn char_to_symbol(c: char) -> i32
 {
 	match c
@@ -526,11 +792,104 @@ pub fn char_to_symbol(c: char) -> i32
 	}
 }
 
+fn check_grid_coords(coords: &[f64], n: usize)
+{
+	assert!(coords.len() == n || coords.len() == n + 1, "Expected {} or {} grid coordinates, got {}", n, n + 1, coords.len());
+	for w in coords.windows(2)
+	{
+		assert!(w[1] > w[0], "Grid coordinates must be strictly increasing");
+	}
+}
+
+fn extend_range(range: &mut (f64, f64), v: f64)
+{
+	if v.is_finite()
+	{
+		range.0 = range.0.min(v);
+		range.1 = range.1.max(v);
+	}
+}
+
+/// Extends the X and Y ranges with a row of data of a plot element. Besides the first two columns, this includes the columns that
+/// extend the element along the axes, like the upper edge of `FillBetween`, the error bars or the sizes of the boxes, circles and ellipses.
+fn extend_row_ranges(plot_type: &PlotType, row: &[f64], x_range: &mut (f64, f64), y_range: &mut (f64, f64))
+{
+	let (x, y) = (row[0], row[1]);
+	let (dx, dy) = match *plot_type
+	{
+		FillBetween =>
+		{
+			extend_range(y_range, row[2]);
+			(0.0, 0.0)
+		},
+		XErrorLines => (row[2], 0.0),
+		YErrorLines => (0.0, row[2]),
+		Boxes => (row[2] / 2.0, 0.0),
+		// The radius is in the units of the X axis, but circles are usually drawn with equal scales
+		Circles => (row[2], row[2]),
+		Ellipses =>
+		{
+			let (a, b) = (row[2] / 2.0, row[3] / 2.0);
+			let (sin, cos) = row[4].to_radians().sin_cos();
+			(((a * cos).powi(2) + (b * sin).powi(2)).sqrt(), ((a * sin).powi(2) + (b * cos).powi(2)).sqrt())
+		},
+		BoxXYError | PaletteBoxXYError => (row[2], row[3]),
+		_ => (0.0, 0.0)
+	};
+	let (dx, dy) = (dx.abs(), dy.abs());
+	extend_range(x_range, x - dx);
+	extend_range(x_range, x + dx);
+	extend_range(y_range, y - dy);
+	extend_range(y_range, y + dy);
+}
+
+/// Converts the coordinates of a non-uniform grid with `n` cells along some axis into the `n + 1` edges of those cells.
+/// The coordinates can either be the `n + 1` edges themselves or the `n` centers of the cells.
+pub fn grid_edges(coords: &[f64], n: usize) -> Vec<f64>
+{
+	check_grid_coords(coords, n);
+	if coords.len() == n + 1
+	{
+		coords.to_vec()
+	}
+	else if n == 1
+	{
+		vec![coords[0] - 0.5, coords[0] + 0.5]
+	}
+	else
+	{
+		let mut edges = Vec::with_capacity(n + 1);
+		edges.push(coords[0] - 0.5 * (coords[1] - coords[0]));
+		for w in coords.windows(2)
+		{
+			edges.push(0.5 * (w[0] + w[1]));
+		}
+		edges.push(coords[n - 1] + 0.5 * (coords[n - 1] - coords[n - 2]));
+		edges
+	}
+}
+
+/// Like `grid_edges`, but returns the `n` centers of the cells.
+pub fn grid_centers(coords: &[f64], n: usize) -> Vec<f64>
+{
+	check_grid_coords(coords, n);
+	if coords.len() == n
+	{
+		coords.to_vec()
+	}
+	else
+	{
+		coords.windows(2).map(|w| 0.5 * (w[0] + w[1])).collect()
+	}
+}
+
 enum DataSourceType
 {
 	Record,
 	Array,
 	SizedArray(f64, f64, f64, f64),
+	Expression,
+	GridRecord(usize, usize),
 }
 
 impl AxesCommonData
@@ -544,9 +903,11 @@ impl AxesCommonData
 			grid_rows: 0,
 			grid_cols: 0,
 			grid_pos: None,
+			has_grid: false,
 			x_axis: AxisData::new(XTickAxis),
 			y_axis: AxisData::new(YTickAxis),
 			cb_axis: AxisData::new(CBTickAxis),
+			data_ranges: [EMPTY_RANGE; 4],
 		}
 	}
 
@@ -583,20 +944,31 @@ impl AxesCommonData
 
 	pub fn write_color_options<'l>(c: &mut Writer, options: &[PlotOption<'l>], default: Option<&'l str>)
 	{
-		let mut col = default;
+		let mut col = None;
 		first_opt!{options,
 			Color(s) =>
 			{
 				col = Some(s)
 			}
 		}
-		match col
+		let mut palette = None;
+		first_opt!{options,
+			PaletteColor(v) =>
+			{
+				palette = Some(v)
+			}
+		}
+		match (col, palette, default)
 		{
-			Some(s) =>
+			(Some(s), _, _) | (None, None, Some(s)) =>
 			{
 				write!(c, r#" lc rgb "{}""#, s);
 			},
-			None => ()
+			(None, Some(v), _) =>
+			{
+				write!(c, " lc palette cb {:.12e}", v);
+			},
+			(None, None, None) => ()
 		}
 	}
 	
@@ -606,17 +978,22 @@ impl AxesCommonData
 		let l = self.elems.len();
 		self.elems.push(PlotElement::new());
 		let mut num_rows = 0;
+		let mut x_range = (f64::INFINITY, f64::NEG_INFINITY);
+		let mut y_range = (f64::INFINITY, f64::NEG_INFINITY);
 
 		{
 			let data = &mut self.elems[l].data;
 			for (x1, x2) in x1.zip(x2)
 			{
+				extend_range(&mut x_range, x1.get());
+				extend_range(&mut y_range, x2.get());
 				data.write_data(x1);
 				data.write_data(x2);
 				num_rows += 1;
 			}
 		}
 
+		self.extend_data_ranges(x_range, y_range, options);
 		self.write_common_commands(l, num_rows, 2, plot_type, Record, false, options);
 	}
 
@@ -627,11 +1004,14 @@ impl AxesCommonData
 		let l = self.elems.len();
 		self.elems.push(PlotElement::new());
 		let mut num_rows = 0;
+		let mut x_range = (f64::INFINITY, f64::NEG_INFINITY);
+		let mut y_range = (f64::INFINITY, f64::NEG_INFINITY);
 
 		{
 			let data = &mut self.elems[l].data;
 			for ((x1, x2), x3) in x1.zip(x2).zip(x3)
 			{
+				extend_row_ranges(&plot_type, &[x1.get(), x2.get(), x3.get()], &mut x_range, &mut y_range);
 				data.write_data(x1);
 				data.write_data(x2);
 				data.write_data(x3);
@@ -639,67 +1019,263 @@ impl AxesCommonData
 			}
 		}
 
+		self.extend_data_ranges(x_range, y_range, options);
 		self.write_common_commands(l, num_rows, 3, plot_type, Record, false, options);
 	}
 
-	pub fn plot_matrix<T: DataType, X: Iterator<Item = T>>(&mut self, plot_type: PlotType, is_3d: bool, mat: X, num_rows: usize, num_cols: usize,
-	                                                dimensions: Option<(f64, f64, f64, f64)>, options: &[PlotOption])
+	pub fn plot4<T1: DataType, X1: Iterator<Item = T1>,
+			     T2: DataType, X2: Iterator<Item = T2>,
+			     T3: DataType, X3: Iterator<Item = T3>,
+			     T4: DataType, X4: Iterator<Item = T4>>(&mut self, plot_type: PlotType, x1: X1, x2: X2, x3: X3, x4: X4, options: &[PlotOption])
 	{
 		let l = self.elems.len();
 		self.elems.push(PlotElement::new());
-		
+		let mut num_rows = 0;
+		let mut x_range = (f64::INFINITY, f64::NEG_INFINITY);
+		let mut y_range = (f64::INFINITY, f64::NEG_INFINITY);
+
 		{
-			let mut count = 0;
 			let data = &mut self.elems[l].data;
-			for x in mat
+			for (((x1, x2), x3), x4) in x1.zip(x2).zip(x3).zip(x4)
 			{
-				data.write_data(x);
-				count += 1;
+				extend_row_ranges(&plot_type, &[x1.get(), x2.get(), x3.get(), x4.get()], &mut x_range, &mut y_range);
+				data.write_data(x1);
+				data.write_data(x2);
+				data.write_data(x3);
+				data.write_data(x4);
+				num_rows += 1;
 			}
-			
-			if count < num_rows * num_cols
+		}
+
+		self.extend_data_ranges(x_range, y_range, options);
+		self.write_common_commands(l, num_rows, 4, plot_type, Record, false, options);
+	}
+
+	pub fn plot5<T1: DataType, X1: Iterator<Item = T1>,
+			     T2: DataType, X2: Iterator<Item = T2>,
+			     T3: DataType, X3: Iterator<Item = T3>,
+			     T4: DataType, X4: Iterator<Item = T4>,
+			     T5: DataType, X5: Iterator<Item = T5>>(&mut self, plot_type: PlotType, x1: X1, x2: X2, x3: X3, x4: X4, x5: X5, options: &[PlotOption])
+	{
+		let l = self.elems.len();
+		self.elems.push(PlotElement::new());
+		let mut num_rows = 0;
+		let mut x_range = (f64::INFINITY, f64::NEG_INFINITY);
+		let mut y_range = (f64::INFINITY, f64::NEG_INFINITY);
+
+		{
+			let data = &mut self.elems[l].data;
+			for ((((x1, x2), x3), x4), x5) in x1.zip(x2).zip(x3).zip(x4).zip(x5)
 			{
-				for _ in 0..num_rows * num_cols - count
-				{
-					use std::f64;
-					data.write_data(f64::NAN);
-				}
+				extend_row_ranges(&plot_type, &[x1.get(), x2.get(), x3.get(), x4.get(), x5.get()], &mut x_range, &mut y_range);
+				data.write_data(x1);
+				data.write_data(x2);
+				data.write_data(x3);
+				data.write_data(x4);
+				data.write_data(x5);
+				num_rows += 1;
 			}
 		}
-		
-		let source_type = match dimensions
-		{
-			Some((x1, y1, x2, y2)) => SizedArray(x1, y1, x2, y2),
-			None => Array
-		};
-		self.write_common_commands(l, num_rows, num_cols, plot_type, source_type, is_3d, options);
+
+		self.extend_data_ranges(x_range, y_range, options);
+		self.write_common_commands(l, num_rows, 5, plot_type, Record, false, options);
 	}
 
-	fn write_common_commands(&mut self, elem_idx: usize, num_rows: usize, num_cols: usize, plot_type: PlotType,
-	                         source_type: DataSourceType, is_3d: bool, options: &[PlotOption])
+	pub fn plot_grid<T: DataType, X: Iterator<Item = T>>(&mut self, plot_type: PlotType, is_3d: bool, mat: X, num_rows: usize, num_cols: usize,
+	                                              x_coords: &[f64], y_coords: &[f64], options: &[PlotOption])
 	{
-		let args = &mut self.elems[elem_idx].args as &mut Writer;
-		match source_type
+		let l = self.elems.len();
+		self.elems.push(PlotElement::new());
+
 		{
-			Record => 
+			let data = &mut self.elems[l].data;
+			let mut mat = mat.map(|v| v.get()).chain(repeat(f64::NAN));
+			for &y in y_coords.iter().take(num_rows)
 			{
-				write!(args, r#" "-" binary endian=little record={} format="%float64" using "#, num_rows);
-			
-				let mut col_idx = 1;
-				while col_idx < num_cols + 1
+				for &x in x_coords.iter().take(num_cols)
 				{
-					write!(args, "{}", col_idx);
-					if col_idx < num_cols
-					{
-						args.write_str(":");
-					}
-					col_idx += 1;
+					data.write_data(x);
+					data.write_data(y);
+					data.write_data(mat.next().unwrap());
 				}
-			},
-			_ =>
-			{
-				write!(args, r#" "-" binary endian=little array=({},{}) format="%float64" "#, num_cols, num_rows);
-				
+			}
+		}
+
+		let mut x_range = (f64::INFINITY, f64::NEG_INFINITY);
+		let mut y_range = (f64::INFINITY, f64::NEG_INFINITY);
+		for &x in x_coords.iter().take(num_cols)
+		{
+			extend_range(&mut x_range, x);
+		}
+		for &y in y_coords.iter().take(num_rows)
+		{
+			extend_range(&mut y_range, y);
+		}
+		self.extend_data_ranges(x_range, y_range, options);
+
+		self.write_common_commands(l, num_rows * num_cols, 3, plot_type, GridRecord(num_cols, num_rows), is_3d, options);
+	}
+
+	pub fn plot_expression(&mut self, plot_type: PlotType, is_3d: bool, expr: &str, options: &[PlotOption])
+	{
+		let l = self.elems.len();
+		self.elems.push(PlotElement::new());
+		write!(&mut self.elems[l].args, " {}", expr);
+		self.write_common_commands(l, 0, 0, plot_type, Expression, is_3d, options);
+	}
+
+	pub fn plot_function(&mut self, f: Box<Fn(f64) -> f64>, x_range: (AutoOption<f64>, AutoOption<f64>), samples: usize, options: &[PlotOption])
+	{
+		let mut x_axis = X1;
+		first_opt!{options,
+			Axes(x, _) =>
+			{
+				x_axis = x;
+			}
+		}
+		let l = self.elems.len();
+		self.elems.push(PlotElement::new());
+		self.elems[l].function = Some(FunctionSource
+		{
+			f: f,
+			x_range: x_range,
+			samples: samples,
+			x_axis: x_axis,
+		});
+		// The data source is only known once the function is sampled, see `write_out_elements`
+		self.write_common_commands(l, 0, 2, Lines, Expression, false, options);
+	}
+
+	pub fn plot_matrix<T: DataType, X: Iterator<Item = T>>(&mut self, plot_type: PlotType, is_3d: bool, mat: X, num_rows: usize, num_cols: usize,
+	                                                dimensions: Option<(f64, f64, f64, f64)>, options: &[PlotOption])
+	{
+		let l = self.elems.len();
+		self.elems.push(PlotElement::new());
+		
+		{
+			let mut count = 0;
+			let num_values = num_rows * num_cols * plot_type.num_channels();
+			let data = &mut self.elems[l].data;
+			for x in mat
+			{
+				data.write_data(x);
+				count += 1;
+			}
+			
+			if count < num_values
+			{
+				for _ in 0..num_values - count
+				{
+					use std::f64;
+					data.write_data(f64::NAN);
+				}
+			}
+		}
+		
+		let (x1, y1, x2, y2) = dimensions.unwrap_or((0.0, 0.0, num_cols as f64 - 1.0, num_rows as f64 - 1.0));
+		self.extend_data_ranges((x1.min(x2), x1.max(x2)), (y1.min(y2), y1.max(y2)), options);
+		let source_type = match dimensions
+		{
+			Some((x1, y1, x2, y2)) => SizedArray(x1, y1, x2, y2),
+			None => Array
+		};
+		self.write_common_commands(l, num_rows, num_cols, plot_type, source_type, is_3d, options);
+	}
+
+	/// Returns the data of the axes that every kind of axes has
+	pub fn get_axis_mut(&mut self, axis: TickAxis) -> Option<&mut AxisData>
+	{
+		match axis
+		{
+			XTickAxis => Some(&mut self.x_axis),
+			YTickAxis => Some(&mut self.y_axis),
+			CBTickAxis => Some(&mut self.cb_axis),
+			_ => None
+		}
+	}
+
+	/// Records the range of the data of a plot element on the axes it is plotted against
+	fn extend_data_ranges(&mut self, x_range: (f64, f64), y_range: (f64, f64), options: &[PlotOption])
+	{
+		let mut axes = (X1, Y1);
+		first_opt!{options,
+			Axes(x, y) =>
+			{
+				axes = (x, y);
+			}
+		}
+		let x_idx = match axes.0
+		{
+			X1 => 0,
+			X2 => 2,
+		};
+		let y_idx = match axes.1
+		{
+			Y1 => 1,
+			Y2 => 3,
+		};
+		for &(idx, (lo, hi)) in [(x_idx, x_range), (y_idx, y_range)].iter()
+		{
+			let range = &mut self.data_ranges[idx];
+			range.0 = range.0.min(lo);
+			range.1 = range.1.max(hi);
+		}
+	}
+
+	/// Returns the minimum and maximum of the data plotted against an axis, or `EMPTY_RANGE` if it is not tracked
+	pub fn data_range(&self, axis: TickAxis) -> (f64, f64)
+	{
+		match axis
+		{
+			XTickAxis => self.data_ranges[0],
+			YTickAxis => self.data_ranges[1],
+			X2TickAxis => self.data_ranges[2],
+			Y2TickAxis => self.data_ranges[3],
+			_ => EMPTY_RANGE
+		}
+	}
+
+	fn write_common_commands(&mut self, elem_idx: usize, num_rows: usize, num_cols: usize, plot_type: PlotType,
+	                         source_type: DataSourceType, is_3d: bool, options: &[PlotOption])
+	{
+		let args = &mut self.elems[elem_idx].args as &mut Writer;
+		match source_type
+		{
+			Record | GridRecord(..) =>
+			{
+				match source_type
+				{
+					GridRecord(grid_cols, grid_rows) =>
+					{
+						write!(args, r#" "-" binary endian=little record=({},{}) format="%float64" using "#, grid_cols, grid_rows);
+					},
+					_ =>
+					{
+						write!(args, r#" "-" binary endian=little record={} format="%float64" using "#, num_rows);
+					}
+				}
+
+				let mut col_idx = 1;
+				while col_idx < num_cols + 1
+				{
+					write!(args, "{}", col_idx);
+					if col_idx < num_cols
+					{
+						args.write_str(":");
+					}
+					col_idx += 1;
+				}
+			},
+			Expression => (),
+			_ =>
+			{
+				write!(args, r#" "-" binary endian=little array=({},{}) format=""#, num_cols, num_rows);
+				for _ in 0..plot_type.num_channels()
+				{
+					args.write_str("%float64");
+				}
+				args.write_str("\" ");
+				
 				match source_type
 				{
 					SizedArray(x1, y1, x2, y2) =>
@@ -749,6 +1325,60 @@ impl AxesCommonData
 			}
 		}
 
+		// Expressions and functions are sampled on the fly, so there is no data to smooth
+		match source_type
+		{
+			Expression => (),
+			_ =>
+			{
+				first_opt!{options,
+					Smooth(s) =>
+					{
+						args.write_str(" smooth ");
+						args.write_str(match s
+						{
+							CSplines => "csplines",
+							ACSplines => "acsplines",
+							Bezier => "bezier",
+							SBezier => "sbezier",
+							Unique => "unique",
+							Frequency => "frequency",
+							Cumulative => "cumulative",
+							CNormal => "cnormal",
+							KDensity(..) => "kdensity",
+						});
+						match s
+						{
+							KDensity(Fix(bw)) =>
+							{
+								write!(args, " bandwidth {:.12e}", bw);
+							},
+							_ => ()
+						}
+					}
+				}
+			}
+		}
+
+		if !is_3d
+		{
+			first_opt!{options,
+				Axes(x, y) =>
+				{
+					write!(args, " axes {}{}", match x
+					{
+						X1 => "x1",
+						X2 => "x2",
+					},
+					match y
+					{
+						Y1 => "y1",
+						Y2 => "y2",
+					});
+				}
+			}
+		}
+
 		args.write_str(" with ");
 		let type_str = match plot_type
 		{
@@ -761,9 +1391,40 @@ impl AxesCommonData
 			Boxes => "boxes",
 			Pm3D => "pm3d",
 			Image => "image",
+			Steps => "steps",
+			FSteps => "fsteps",
+			HiSteps => "histeps",
+			Impulses => "impulses",
+			PalettePoints => "points",
+			RGBPoints => "points",
+			BoxXYError => "boxxyerror",
+			Circles => "circles",
+			Ellipses => "ellipses",
+			RGBImage => "rgbimage",
+			RGBAlphaImage => "rgbalpha",
+			PaletteBoxXYError => "boxxyerror",
 		};
 		args.write_str(type_str);
 
+		match plot_type
+		{
+			Ellipses =>
+			{
+				first_opt!{options,
+					EllipseUnits(u) =>
+					{
+						args.write_str(match u
+						{
+							UnitsXY => " units xy",
+							UnitsXX => " units xx",
+							UnitsYY => " units yy",
+						});
+					}
+				}
+			},
+			_ => ()
+		}
+
 		if plot_type.is_fill()
 		{
 			match plot_type
@@ -830,15 +1491,33 @@ impl AxesCommonData
 				}
 			}
 
-			first_opt!{options,
-				PointSize(z) =>
-				{
-					write!(args, " ps {}", z);
+			if plot_type.is_variable_color()
+			{
+				args.write_str(" ps variable");
+			}
+			else
+			{
+				first_opt!{options,
+					PointSize(z) =>
+					{
+						write!(args, " ps {}", z);
+					}
 				}
 			}
 		}
 
-		AxesCommonData::write_color_options(args, options, None);
+		match plot_type
+		{
+			PalettePoints | PaletteBoxXYError =>
+			{
+				args.write_str(" lc palette");
+			},
+			RGBPoints =>
+			{
+				args.write_str(" lc rgb variable");
+			},
+			_ => AxesCommonData::write_color_options(args, options, None)
+		}
 
 		args.write_str(" t \"");
 		first_opt!{options,
@@ -853,60 +1532,179 @@ impl AxesCommonData
 	pub fn write_out_commands(&self, writer: &mut Writer)
 	{
 		writer.write_all(&self.commands[..]);
-		self.x_axis.write_out_commands(writer);
-		self.y_axis.write_out_commands(writer);
-		self.cb_axis.write_out_commands(writer);
+		self.x_axis.write_out_commands(writer, self.data_range(XTickAxis));
+		self.y_axis.write_out_commands(writer, self.data_range(YTickAxis));
+		self.cb_axis.write_out_commands(writer, EMPTY_RANGE);
 	}
 
-	pub fn write_out_elements(&self, cmd: &str, writer: &mut Writer)
+	/// Writes out the plot command and the data of the elements. `x2_axis` is the secondary X axis, for the axes that have one.
+	pub fn write_out_elements(&self, cmd: &str, x2_axis: Option<&AxisData>, writer: &mut Writer)
 	{
+		// Functions are sampled now, so that the ranges of the axes and all the other data are known
+		let sampled: Vec<Option<Vec<[f64; 3]>>> = self.elems.iter().map(|e| e.function.as_ref().map(|func|
+		{
+			match (func.x_axis, x2_axis)
+			{
+				(X2, Some(x2_axis)) => func.sample(x2_axis, self.data_range(X2TickAxis)),
+				_ => func.sample(&self.x_axis, self.data_range(XTickAxis)),
+			}
+		})).collect();
+
 		write!(writer, "{}", cmd);
 
 		let mut first = true;
-		for e in self.elems.iter()
+		for (e, points) in self.elems.iter().zip(sampled.iter())
 		{
 			if !first
 			{
 				write!(writer, ",");
 			}
+			match *points
+			{
+				Some(ref points) =>
+				{
+					write!(writer, r#" "-" binary endian=little record={} format="%float64" using 1:2"#, points.len());
+				},
+				None => ()
+			}
 			writer.write_all(&e.args[..]);
 			first = false;
 		}
 
 		write!(writer, "\n");
 
-		for e in self.elems.iter()
+		for (e, points) in self.elems.iter().zip(sampled.iter())
 		{
-			writer.write_all(&e.data[..]);
+			match *points
+			{
+				Some(ref points) =>
+				{
+					let mut data = vec![];
+					for p in points.iter()
+					{
+						data.write_data(p[0]);
+						data.write_data(p[1]);
+					}
+					writer.write_all(&data[..]);
+				},
+				None =>
+				{
+					writer.write_all(&e.data[..]);
+				}
+			}
 		}
 	}
 
-	pub fn set_label_common(&mut self, label_type: LabelType, text: &str, options: &[LabelOption])
+	/// Writes the layer, fill and border of a `set object` command
+	pub fn write_object_options(c: &mut Writer, options: &[PlotOption])
 	{
-		let c = &mut self.commands;
+		first_opt_default!{options,
+			Layer(l) =>
+			{
+				c.write_str(match l
+				{
+					Front => " front",
+					Back => " back",
+					Behind => " behind",
+				});
+			},
+			_ =>
+			{
+				// An opaque shape in front would hide the data
+				c.write_str(" back");
+			}
+		}
 
-		c.write_str("set ");
+		first_opt!{options,
+			Color(s) =>
+			{
+				write!(c, r#" fc rgb "{}""#, s);
+			}
+		}
 
-		let label_str = match label_type
+		c.write_str(" fs transparent");
+		first_opt_default!{options,
+			FillPattern(p) =>
+			{
+				write!(c, " pattern {}", p.to_int());
+			},
+			_ =>
+			{
+				c.write_str(" solid ");
+				first_opt_default!{options,
+					FillAlpha(a) =>
+					{
+						write!(c, "{:.12e}", a);
+					},
+					_ =>
+					{
+						c.write_str("1");
+					}
+				}
+			}
+		}
+
+		let mut have_border = false;
+		first_opt!{options,
+			BorderColor(s) =>
+			{
+				write!(c, r#" border rgb "{}""#, s);
+				have_border = true;
+			}
+		}
+		if have_border
 		{
-			XLabel => "xlabel",
-			YLabel => "ylabel",
-			ZLabel => "zlabel",
-			CBLabel => "cblabe",
-			TitleLabel => "title",
-			Label(..) => "label",
-			_ => panic!("Invalid label type")
-		};
-		c.write_str(label_str);
+			AxesCommonData::write_line_options(c, options);
+		}
+		else
+		{
+			c.write_str(" noborder");
+		}
+	}
+
+	pub fn set_label_common(&mut self, label_type: LabelType, text: &str, options: &[LabelOption])
+	{
+		write_label(&mut self.commands, label_type, text, options);
+	}
+}
 
-		c.write_str(" \"");
-		c.write_str(text);
-		c.write_str("\"");
+/// Escapes the backslashes and quotes of a string, so that it can be placed between the double quotes of a gnuplot string
+pub fn escape(s: &str) -> String
+{
+	s.replace("\\", "\\\\").replace("\"", "\\\"")
+}
 
-		write_out_label_options(label_type, options, c);
+/// Writes a `set label` (or `set xlabel`, `set title` etc.) command
+pub fn write_label(c: &mut Vec<u8>, label_type: LabelType, text: &str, options: &[LabelOption])
+{
+	write_label_expr(c, label_type, &format!("\"{}\"", text)[..], options);
+}
 
-		c.write_str("\n");
-	}
+/// Like `write_label`, but the text is given as a gnuplot string expression
+pub fn write_label_expr(c: &mut Vec<u8>, label_type: LabelType, text_expr: &str, options: &[LabelOption])
+{
+	c.write_str("set ");
+
+	let label_str = match label_type
+	{
+		XLabel => "xlabel",
+		YLabel => "ylabel",
+		X2Label => "x2label",
+		Y2Label => "y2label",
+		ZLabel => "zlabel",
+		CBLabel => "cblabe",
+		TitleLabel => "title",
+		Label(..) => "label",
+		_ => panic!("Invalid label type")
+	};
+	c.write_str(label_str);
+
+	c.write_str(" ");
+	c.write_str(text_expr);
+
+	write_out_label_options(label_type, options, c);
+
+	c.write_str("\n");
 }
 
 #[doc(hidden)]
@@ -914,6 +1712,8 @@ pub trait AxesCommonPrivate
 {
 	fn get_common_data<'l>(&'l self) -> &'l AxesCommonData;
 	fn get_common_data_mut<'l>(&'l mut self) -> &'l mut AxesCommonData;
+	/// Returns the data of an axis, or None if this kind of axes doesn't have it
+	fn get_axis_mut<'l>(&'l mut self, axis: TickAxis) -> Option<&'l mut AxisData>;
 }
 
 pub trait AxesCommon : AxesCommonPrivate
@@ -983,6 +1783,103 @@ pub trait AxesCommon : AxesCommonPrivate
 		self
 	}
 
+	/// Sets the number of samples used to evaluate expressions (see `Axes2D::expression` and `Axes3D::expression_surface`).
+	/// # Arguments
+	/// * `x_samples` - Number of samples along the X axis
+	/// * `y_samples` - Number of samples along the Y axis (only relevant for 3D axes)
+	fn set_samples<'l>(&'l mut self, x_samples: u32, y_samples: u32) -> &'l mut Self
+	{
+		assert!(x_samples > 1 && y_samples > 1, "Need at least 2 samples");
+		writeln!(&mut self.get_common_data_mut().commands, "set samples {},{}", x_samples, y_samples);
+		self
+	}
+
+	/// Draws grid lines at the positions of the ticks of the specified axes. Replaces the grid set by previous calls to this method.
+	///
+	/// # Arguments
+	/// * `axes` - Which axes' ticks to draw grid lines for. `TTickAxis` draws the polar grid, the angle between its radial lines can be changed
+	///            using `Axes2D::set_polar_grid`. Pass an empty slice to remove the grid.
+	/// * `minor` - Whether to also draw grid lines at the positions of the minor ticks. For the polar grid, these are circles at the minor radial ticks
+	/// * `front` - Whether to draw the grid above or below the plot elements
+	/// * `major_options` - Array of PlotOption controlling the appearance of the major grid lines. Relevant options are:
+	///      * `Color` - Specifies the color of the grid lines
+	///      * `LineStyle` - Specifies the style of the grid lines
+	///      * `LineWidth` - Specifies the width of the grid lines
+	/// * `minor_options` - Like `major_options`, but for the minor grid lines
+	fn set_grid<'l>(&'l mut self, axes: &[TickAxis], minor: bool, front: bool, major_options: &[PlotOption], minor_options: &[PlotOption]) -> &'l mut Self
+	{
+		self.get_common_data_mut().has_grid = !axes.is_empty();
+		{
+			let c = &mut self.get_common_data_mut().commands as &mut Writer;
+			c.write_str("unset grid\n");
+			if !axes.is_empty()
+			{
+				c.write_str("set grid");
+				for axis in axes.iter()
+				{
+					match *axis
+					{
+						TTickAxis =>
+						{
+							c.write_str(" polar");
+							// The circles of the polar grid are drawn at the radial ticks
+							if minor && !axes.iter().any(|a| match *a { RTickAxis => true, _ => false })
+							{
+								c.write_str(" mrtics");
+							}
+						},
+						ref axis =>
+						{
+							write!(c, " {}", axis.to_tick_str());
+							if minor
+							{
+								write!(c, " m{}", axis.to_tick_str());
+							}
+						}
+					}
+				}
+				c.write_str(if front
+				{
+					" front"
+				}
+				else
+				{
+					" back"
+				});
+
+				AxesCommonData::write_color_options(c, major_options, Some("gray"));
+				AxesCommonData::write_line_options(c, major_options);
+				c.write_str(",");
+				AxesCommonData::write_color_options(c, minor_options, Some("gray"));
+				AxesCommonData::write_line_options(c, minor_options);
+
+				c.write_str("\n");
+			}
+		}
+		self
+	}
+
+	/// Computes summary statistics of a dataset using gnuplot's `stats` command, which runs a separate gnuplot process and waits for it to finish.
+	/// Requires gnuplot 5.0 or later. Values that are not finite are skipped. If gnuplot fails, the error message it reported is returned instead.
+	/// # Arguments
+	/// * `data` - Iterator for the values
+	fn stats<T: DataType, D: Iterator<Item = T>>(&self, data: D) -> Result<Stats, String>
+	{
+		::query::stats(data)
+	}
+
+	/// Computes summary statistics of paired data using gnuplot's `stats` command, which runs a separate gnuplot process and waits for it to finish.
+	/// Requires gnuplot 5.0 or later. Points where either value is not finite are skipped, as are points outside of the X and Y ranges of these axes
+	/// (the same ones that are dropped when plotting), so set the ranges before calling this. If gnuplot fails, the error message it reported is returned instead.
+	/// # Arguments
+	/// * `x` - Iterator for the x values
+	/// * `y` - Iterator for the y values
+	fn stats_xy<Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(&self, x: X, y: Y) -> Result<StatsXY, String>
+	{
+		let c = self.get_common_data();
+		::query::stats_xy(x, y, (c.x_axis.min, c.x_axis.max), (c.y_axis.min, c.y_axis.max))
+	}
+
 	/// Set the label for the X axis
 	/// # Arguments
 	/// * `text` - Text of the label. Pass an empty string to hide the label
@@ -1047,6 +1944,81 @@ pub trait AxesCommon : AxesCommonPrivate
 		self
 	}
 
+	/// Draws a rectangle with corners at `(x1, y1)` and `(x2, y2)`.
+	/// # Arguments
+	/// * `x1`, `y1` - Coordinates of one corner
+	/// * `x2`, `y2` - Coordinates of the opposite corner
+	/// * `options` - Array of PlotOption controlling the appearance of the shape. Relevant options are:
+	///      * `Color` - Specifies the fill color
+	///      * `FillAlpha` - Sets the transparency of the fill
+	///      * `FillPattern` - Fills the shape with a pattern instead
+	///      * `BorderColor` - Draws a border with this color. Without it, no border is drawn
+	///      * `LineWidth` - Sets the width of the border
+	///      * `LineStyle` - Sets the style of the border
+	///      * `Layer` - Sets whether the shape is drawn in front of or behind (default) the plot elements
+	fn rectangle<'l>(&'l mut self, x1: Coordinate, y1: Coordinate, x2: Coordinate, y2: Coordinate, options: &[PlotOption]) -> &'l mut Self
+	{
+		{
+			let c = &mut self.get_common_data_mut().commands as &mut Writer;
+			write!(c, "set object rectangle from {},{} to {},{}", x1, y1, x2, y2);
+			AxesCommonData::write_object_options(c, options);
+			c.write_str("\n");
+		}
+		self
+	}
+
+	/// Draws a circle centered at `(x, y)`. See `rectangle` for the relevant options.
+	/// # Arguments
+	/// * `x`, `y` - Coordinates of the center
+	/// * `radius` - The radius of the circle. It is measured along the X axis, so e.g. `Graph(0.1)` is a tenth of the width of the graph
+	fn circle<'l>(&'l mut self, x: Coordinate, y: Coordinate, radius: Coordinate, options: &[PlotOption]) -> &'l mut Self
+	{
+		{
+			let c = &mut self.get_common_data_mut().commands as &mut Writer;
+			write!(c, "set object circle at {},{} size{}", x, y, radius);
+			AxesCommonData::write_object_options(c, options);
+			c.write_str("\n");
+		}
+		self
+	}
+
+	/// Draws an ellipse centered at `(x, y)`. See `rectangle` for the relevant options.
+	/// # Arguments
+	/// * `x`, `y` - Coordinates of the center
+	/// * `width` - The length of the first axis of the ellipse (along the X axis before rotation)
+	/// * `height` - The length of the second axis of the ellipse (along the Y axis before rotation)
+	/// * `angle` - Rotation of the ellipse (in degrees, counter-clockwise)
+	fn ellipse<'l>(&'l mut self, x: Coordinate, y: Coordinate, width: Coordinate, height: Coordinate, angle: f64, options: &[PlotOption]) -> &'l mut Self
+	{
+		{
+			let c = &mut self.get_common_data_mut().commands as &mut Writer;
+			write!(c, "set object ellipse at {},{} size{},{} angle {:.12e}", x, y, width, height, angle);
+			AxesCommonData::write_object_options(c, options);
+			c.write_str("\n");
+		}
+		self
+	}
+
+	/// Draws a closed polygon through the vertices. See `rectangle` for the relevant options.
+	/// # Arguments
+	/// * `vertices` - Iterator of the coordinates of the vertices. At least 3 are required
+	fn polygon<'l, V: Iterator<Item = (Coordinate, Coordinate)>>(&'l mut self, vertices: V, options: &[PlotOption]) -> &'l mut Self
+	{
+		let vertices: Vec<(Coordinate, Coordinate)> = vertices.collect();
+		assert!(vertices.len() >= 3, "A polygon needs at least 3 vertices");
+		{
+			let c = &mut self.get_common_data_mut().commands as &mut Writer;
+			write!(c, "set object polygon from {},{}", vertices[0].0, vertices[0].1);
+			for &(x, y) in vertices[1..].iter().chain(vertices[..1].iter())
+			{
+				write!(c, " to {},{}", x, y);
+			}
+			AxesCommonData::write_object_options(c, options);
+			c.write_str("\n");
+		}
+		self
+	}
+
 	/// Sets the properties of the ticks on the X axis.
 	///
 	/// # Arguments
@@ -1115,6 +2087,64 @@ pub trait AxesCommon : AxesCommonPrivate
 		self
 	}
 
+	/// Sets how the tick labels on the X axis are formatted.
+	///
+	/// # Arguments
+	/// * `format` - The format of the labels, see `TickFormat`
+	fn set_x_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Self
+	{
+		self.get_common_data_mut().x_axis.set_format(format);
+		self
+	}
+
+	/// Sets ticks on the X axis at automatically chosen round values, labeled by calling a function with the position of each tick.
+	/// The positions are picked to cover the range of the axis, or the range of the data plotted against it if the range is not fixed.
+	/// Replaces any previously set ticks.
+	///
+	/// # Arguments
+	/// * `formatter` - Function that produces the label for a tick at a given position (e.g. to add units, or format durations)
+	/// * `tick_options` - Array of TickOption controlling the appearance of the ticks
+	/// * `label_options` - Array of LabelOption controlling the appearance of the tick labels. Relevant options are:
+	///      * `Offset` - Specifies the offset of the label
+	///      * `Font` - Specifies the font of the label
+	///      * `TextColor` - Specifies the color of the label
+	///      * `Rotate` - Specifies the rotation of the label
+	///      * `Align` - Specifies how to align the label
+	fn set_x_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Self
+	{
+		self.get_common_data_mut().x_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_tick_formatter` but for the Y axis.
+	fn set_y_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Self
+	{
+		self.get_common_data_mut().y_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
+	/// Like `set_x_tick_format` but for the Y axis.
+	fn set_y_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Self
+	{
+		self.get_common_data_mut().y_axis.set_format(format);
+		self
+	}
+
+	/// Like `set_x_tick_format` but for the color bar axis.
+	fn set_cb_tick_format<'l>(&'l mut self, format: TickFormat) -> &'l mut Self
+	{
+		self.get_common_data_mut().cb_axis.set_format(format);
+		self
+	}
+
+	/// Like `set_x_tick_formatter` but for the color bar axis. The range of the plotted data is not tracked for this axis,
+	/// so the ticks cover the range set by `set_cb_range`, falling back to -10 to 10 for the ends that are not fixed.
+	fn set_cb_tick_formatter<'l, F: Fn(f64) -> String + 'static>(&'l mut self, formatter: F, tick_options: &[TickOption], label_options: &[LabelOption]) -> &'l mut Self
+	{
+		self.get_common_data_mut().cb_axis.set_formatter(Box::new(formatter), tick_options, label_options);
+		self
+	}
+
 	/// Set the range of values for the X axis.
 	///
 	/// # Arguments
@@ -1148,6 +2178,22 @@ pub trait AxesCommon : AxesCommonPrivate
 		self
 	}
 
+	/// Sets options that control the range of an axis, replacing the previously set ones.
+	///
+	/// # Arguments
+	/// * `axis` - Which axis to set the options for. 2D axes have the X, Y, X2, Y2, R and CB axes, while 3D axes have the X, Y, Z and CB axes.
+	///            Passing any other axis panics. Use `Axes2D::set_polar` to change the direction of the angular axis.
+	/// * `options` - Array of RangeOption, see its documentation for the available options
+	fn set_range_options<'l>(&'l mut self, axis: TickAxis, options: &[RangeOption]) -> &'l mut Self
+	{
+		match self.get_axis_mut(axis)
+		{
+			Some(axis_data) => axis_data.set_range_options(options),
+			None => panic!("These axes don't have the '{}' axis", axis.to_axis_str()),
+		}
+		self
+	}
+
 	/// Sets the X axis be logarithmic. Note that the range must be non-negative for this to be valid.
 	///
 	/// # Arguments
@@ -0,0 +1,685 @@
+// Copyright (c) 2013-2014 by SiegeLord
+//
+// All rights reserved. Distributed under LGPL 3.0. For full terms see the file LICENSE.
+
+use std::collections::HashMap;
+use std::f64;
+
+use datatype::*;
+use options::*;
+use ticks::*;
+
+/// A single contour line (a line of equal value)
+pub struct ContourLine
+{
+	/// The value along the line
+	pub level: f64,
+	/// The points of the line. If the line is closed, the first and last points are the same
+	pub points: Vec<(f64, f64)>,
+}
+
+impl ContourLine
+{
+	/// Returns the point halfway along the line, measured by its length
+	pub fn midpoint(&self) -> (f64, f64)
+	{
+		let dist = |a: (f64, f64), b: (f64, f64)| ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+		let total = self.points.windows(2).fold(0.0, |l, w| l + dist(w[0], w[1]));
+		let mut remaining = 0.5 * total;
+		for w in self.points.windows(2)
+		{
+			let l = dist(w[0], w[1]);
+			if l > 0.0 && remaining <= l
+			{
+				let t = remaining / l;
+				return (w[0].0 + t * (w[1].0 - w[0].0), w[0].1 + t * (w[1].1 - w[0].1));
+			}
+			remaining -= l;
+		}
+		self.points[0]
+	}
+}
+
+/// Identifies a grid edge: a horizontal edge goes from `(row, col)` to `(row, col + 1)`, a vertical one from `(row, col)` to `(row + 1, col)`
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum Edge
+{
+	Horizontal(usize, usize),
+	Vertical(usize, usize),
+}
+
+struct Grid<'l>
+{
+	mat: &'l [f64],
+	num_rows: usize,
+	num_cols: usize,
+	dimensions: (f64, f64, f64, f64),
+}
+
+impl<'l> Grid<'l>
+{
+	fn get(&self, row: usize, col: usize) -> f64
+	{
+		self.mat[row * self.num_cols + col]
+	}
+
+	fn to_coords(&self, row: f64, col: f64) -> (f64, f64)
+	{
+		let (x1, y1, x2, y2) = self.dimensions;
+		let dx = if self.num_cols > 1 { (x2 - x1) / (self.num_cols - 1) as f64 } else { 1.0 };
+		let dy = if self.num_rows > 1 { (y2 - y1) / (self.num_rows - 1) as f64 } else { 1.0 };
+		(x1 + col * dx, y1 + row * dy)
+	}
+
+	/// Where the contour at `level` crosses the edge
+	fn crossing(&self, edge: Edge, level: f64) -> (f64, f64)
+	{
+		let (row, col, a, b, horizontal) = match edge
+		{
+			Edge::Horizontal(r, c) => (r, c, self.get(r, c), self.get(r, c + 1), true),
+			Edge::Vertical(r, c) => (r, c, self.get(r, c), self.get(r + 1, c), false),
+		};
+		let t = if b != a { (level - a) / (b - a) } else { 0.5 };
+		if horizontal
+		{
+			self.to_coords(row as f64, col as f64 + t)
+		}
+		else
+		{
+			self.to_coords(row as f64 + t, col as f64)
+		}
+	}
+}
+
+/// Follows the unused segments starting at the `start` edge, appending the edges they lead to to the chain
+fn extend_chain(chain: &mut Vec<Edge>, start: Edge, segments: &[(Edge, Edge)], edge_segments: &HashMap<Edge, Vec<usize>>, used: &mut [bool])
+{
+	let mut cur = start;
+	loop
+	{
+		let next = edge_segments[&cur].iter().cloned().find(|&s| !used[s]);
+		match next
+		{
+			Some(s) =>
+			{
+				used[s] = true;
+				let (a, b) = segments[s];
+				cur = if a == cur { b } else { a };
+				chain.push(cur);
+			},
+			None => break
+		}
+	}
+}
+
+/// Computes the contour lines at a single level using the marching squares algorithm
+fn trace_level(grid: &Grid, level: f64) -> Vec<ContourLine>
+{
+	let mut segments: Vec<(Edge, Edge)> = vec![];
+	for i in 0..grid.num_rows.saturating_sub(1)
+	{
+		for j in 0..grid.num_cols.saturating_sub(1)
+		{
+			let v = [grid.get(i, j), grid.get(i, j + 1), grid.get(i + 1, j + 1), grid.get(i + 1, j)];
+			if v.iter().any(|v| !v.is_finite())
+			{
+				continue;
+			}
+
+			let bottom = Edge::Horizontal(i, j);
+			let right = Edge::Vertical(i, j + 1);
+			let top = Edge::Horizontal(i + 1, j);
+			let left = Edge::Vertical(i, j);
+
+			let mut case = 0;
+			for (k, &v) in v.iter().enumerate()
+			{
+				if v >= level
+				{
+					case |= 1 << k;
+				}
+			}
+			let center_above = (v[0] + v[1] + v[2] + v[3]) / 4.0 >= level;
+
+			match case
+			{
+				1 | 14 => segments.push((left, bottom)),
+				2 | 13 => segments.push((bottom, right)),
+				3 | 12 => segments.push((left, right)),
+				4 | 11 => segments.push((right, top)),
+				6 | 9 => segments.push((bottom, top)),
+				7 | 8 => segments.push((top, left)),
+				5 =>
+				{
+					if center_above
+					{
+						segments.push((bottom, right));
+						segments.push((top, left));
+					}
+					else
+					{
+						segments.push((left, bottom));
+						segments.push((right, top));
+					}
+				},
+				10 =>
+				{
+					if center_above
+					{
+						segments.push((left, bottom));
+						segments.push((right, top));
+					}
+					else
+					{
+						segments.push((bottom, right));
+						segments.push((top, left));
+					}
+				},
+				_ => ()
+			}
+		}
+	}
+
+	// A segment whose ends both sit on the same grid point (where a datapoint equals the level exactly) has zero length
+	segments.retain(|&(a, b)| grid.crossing(a, level) != grid.crossing(b, level));
+
+	// Join the segments that share edges into lines
+	let mut edge_segments: HashMap<Edge, Vec<usize>> = HashMap::new();
+	for (idx, &(a, b)) in segments.iter().enumerate()
+	{
+		edge_segments.entry(a).or_default().push(idx);
+		edge_segments.entry(b).or_default().push(idx);
+	}
+
+	let mut used = vec![false; segments.len()];
+	let mut lines = vec![];
+	for start in 0..segments.len()
+	{
+		if used[start]
+		{
+			continue;
+		}
+		used[start] = true;
+		let (a, b) = segments[start];
+		let mut forward = vec![a, b];
+		let mut backward = vec![];
+		extend_chain(&mut forward, b, &segments, &edge_segments, &mut used);
+		extend_chain(&mut backward, a, &segments, &edge_segments, &mut used);
+
+		let points = backward.iter().rev().chain(forward.iter()).map(|&e| grid.crossing(e, level)).collect();
+		lines.push(ContourLine
+		{
+			level,
+			points,
+		});
+	}
+	lines
+}
+
+/// Clips a polygon, whose vertices are `(x, y, value)`, to the part where the value is above (or below) the level,
+/// assuming the value varies linearly along the edges
+fn clip_polygon(poly: &[(f64, f64, f64)], level: f64, keep_above: bool) -> Vec<(f64, f64, f64)>
+{
+	let inside = |p: &(f64, f64, f64)| if keep_above { p.2 >= level } else { p.2 <= level };
+	let intersect = |a: (f64, f64, f64), b: (f64, f64, f64)|
+	{
+		let t = (level - a.2) / (b.2 - a.2);
+		(a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1), level)
+	};
+
+	let mut out = vec![];
+	for i in 0..poly.len()
+	{
+		let prev = poly[(i + poly.len() - 1) % poly.len()];
+		let cur = poly[i];
+		if inside(&cur)
+		{
+			if !inside(&prev)
+			{
+				out.push(intersect(prev, cur));
+			}
+			out.push(cur);
+		}
+		else if inside(&prev)
+		{
+			out.push(intersect(prev, cur));
+		}
+	}
+	out
+}
+
+/// For a saddle cell (where diagonally opposite corners are on the same side of the level), returns which diagonal the cell has to be cut along
+/// to separate the corners that `trace_level` treats as separate: `Some(0)` for the one from corner 0 to corner 2, `Some(1)` for the other one
+fn saddle_cut(v: &[f64; 4], level: f64, keep_above: bool) -> Option<usize>
+{
+	let center_above = (v[0] + v[1] + v[2] + v[3]) / 4.0 >= level;
+	match (v[0] >= level, v[1] >= level, v[2] >= level, v[3] >= level)
+	{
+		// Corners 0 and 2 are above the level
+		(true, false, true, false) => match (keep_above, center_above)
+		{
+			(true, false) => Some(1),
+			(false, true) => Some(0),
+			_ => None
+		},
+		// Corners 1 and 3 are above the level
+		(false, true, false, true) => match (keep_above, center_above)
+		{
+			(true, false) => Some(0),
+			(false, true) => Some(1),
+			_ => None
+		},
+		_ => None
+	}
+}
+
+/// Computes the polygons covering the part of a row-major 2D array where the values are between `lo` and `hi`.
+/// The polygon edges match the contour lines computed by `compute_contour_lines` at these levels.
+pub fn compute_band_polygons(mat: &[f64], num_rows: usize, num_cols: usize, dimensions: (f64, f64, f64, f64), lo: f64, hi: f64) -> Vec<Vec<(f64, f64)>>
+{
+	assert!(mat.len() >= num_rows * num_cols, "Not enough data for a {}x{} array", num_rows, num_cols);
+	let grid = Grid
+	{
+		mat,
+		num_rows,
+		num_cols,
+		dimensions,
+	};
+
+	let mut polygons = vec![];
+	for i in 0..num_rows.saturating_sub(1)
+	{
+		for j in 0..num_cols.saturating_sub(1)
+		{
+			let idx = [(i, j), (i, j + 1), (i + 1, j + 1), (i + 1, j)];
+			let v = [grid.get(i, j), grid.get(i, j + 1), grid.get(i + 1, j + 1), grid.get(i + 1, j)];
+			if v.iter().any(|v| !v.is_finite()) || v.iter().all(|&v| v < lo) || v.iter().all(|&v| v > hi)
+			{
+				continue;
+			}
+			let corners: Vec<(f64, f64, f64)> = idx.iter().zip(v.iter()).map(|(&(r, c), &v)|
+			{
+				let (x, y) = grid.to_coords(r as f64, c as f64);
+				(x, y, v)
+			}).collect();
+
+			let pieces = match saddle_cut(&v, lo, true).or(saddle_cut(&v, hi, false))
+			{
+				Some(0) => vec![vec![corners[0], corners[1], corners[2]], vec![corners[0], corners[2], corners[3]]],
+				Some(_) => vec![vec![corners[0], corners[1], corners[3]], vec![corners[1], corners[2], corners[3]]],
+				None => vec![corners],
+			};
+			for piece in pieces.iter()
+			{
+				let clipped = clip_polygon(&clip_polygon(&piece[..], lo, true)[..], hi, false);
+				if clipped.len() >= 3
+				{
+					polygons.push(clipped.iter().map(|&(x, y, _)| (x, y)).collect());
+				}
+			}
+		}
+	}
+	polygons
+}
+
+/// Interpolates the points with a Catmull-Rom cubic spline, using `num_points` points per segment
+fn cubic_spline(points: &[(f64, f64)], num_points: u32) -> Vec<(f64, f64)>
+{
+	if points.len() < 3
+	{
+		return points.to_vec();
+	}
+	let closed = points[0] == points[points.len() - 1];
+	let n = points.len();
+	let get = |i: isize| -> (f64, f64)
+	{
+		if closed
+		{
+			// The last point duplicates the first one
+			let m = (n - 1) as isize;
+			points[(((i % m) + m) % m) as usize]
+		}
+		else
+		{
+			points[if i < 0 { 0 } else if i >= n as isize { n - 1 } else { i as usize }]
+		}
+	};
+
+	let mut out = vec![points[0]];
+	for i in 0..(n - 1) as isize
+	{
+		let (p0, p1, p2, p3) = (get(i - 1), get(i), get(i + 1), get(i + 2));
+		for k in 1..num_points + 1
+		{
+			let t = k as f64 / num_points as f64;
+			let (t2, t3) = (t * t, t * t * t);
+			let interp = |a: f64, b: f64, c: f64, d: f64| 0.5 * (2.0 * b + (c - a) * t + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2 + (3.0 * b - a - 3.0 * c + d) * t3);
+			out.push((interp(p0.0, p1.0, p2.0, p3.0), interp(p0.1, p1.1, p2.1, p3.1)));
+		}
+	}
+	out
+}
+
+/// Approximates the points with a B-spline of the given order, using `num_points` points per segment. Open lines use a clamped spline, so that
+/// the ends of the line stay in place, while closed lines (where the first and last points are the same) use a periodic spline, so that they stay closed
+/// without a kink at the first point.
+fn b_spline(points: &[(f64, f64)], num_points: u32, order: u32) -> Vec<(f64, f64)>
+{
+	let n = points.len();
+	if n < 3
+	{
+		return points.to_vec();
+	}
+	let closed = points[0] == points[n - 1];
+	let (control, degree, num_segments, knots): (Vec<(f64, f64)>, usize, usize, Vec<f64>) = if closed
+	{
+		// Wrap the control points around, with uniform knots
+		let m = n - 1;
+		let degree = (order as usize - 1).min(m - 1);
+		let control = (0..m + degree).map(|i| points[i % m]).collect();
+		let knots = (0..m + 2 * degree + 1).map(|i| i as f64 - degree as f64).collect();
+		(control, degree, m, knots)
+	}
+	else
+	{
+		// Repeat the end knots, so that the spline starts and ends at the end points
+		let degree = (order as usize - 1).min(n - 1);
+		let num_segments = n - degree;
+		let knots = (0..n + degree + 1).map(|i|
+		{
+			if i <= degree
+			{
+				0.0
+			}
+			else if i >= n
+			{
+				num_segments as f64
+			}
+			else
+			{
+				(i - degree) as f64
+			}
+		}).collect();
+		(points.to_vec(), degree, num_segments, knots)
+	};
+
+	let mut out = vec![];
+	for seg in 0..num_segments
+	{
+		let first = if seg == 0 { 0 } else { 1 };
+		for k in first..num_points + 1
+		{
+			let u = seg as f64 + k as f64 / num_points as f64;
+			// de Boor's algorithm for the knot span [seg + degree, seg + degree + 1)
+			let span = seg + degree;
+			let mut d: Vec<(f64, f64)> = (0..degree + 1).map(|j| control[j + span - degree]).collect();
+			for r in 1..degree + 1
+			{
+				for j in (r..degree + 1).rev()
+				{
+					let i = j + span - degree;
+					let denom = knots[i + degree + 1 - r] - knots[i];
+					let alpha = if denom > 0.0 { (u - knots[i]) / denom } else { 0.0 };
+					d[j] = ((1.0 - alpha) * d[j - 1].0 + alpha * d[j].0, (1.0 - alpha) * d[j - 1].1 + alpha * d[j].1);
+				}
+			}
+			out.push(d[degree]);
+		}
+	}
+	if closed
+	{
+		// Make the line exactly closed, despite rounding
+		out[0] = out[out.len() - 1];
+	}
+	out
+}
+
+fn clamp(val: u32, min: u32, max: u32) -> u32
+{
+	if val < min
+	{
+		min
+	}
+	else if val > max
+	{
+		max
+	}
+	else
+	{
+		val
+	}
+}
+
+fn apply_style(mut lines: Vec<ContourLine>, style: ContourStyle) -> Vec<ContourLine>
+{
+	for line in lines.iter_mut()
+	{
+		line.points = match style
+		{
+			Linear => continue,
+			Cubic(pt) => cubic_spline(&line.points[..], clamp(pt, 2, 100)),
+			Spline(pt, ord) => b_spline(&line.points[..], clamp(pt, 2, 100), clamp(ord, 2, 10)),
+		};
+	}
+	lines
+}
+
+fn collect_matrix<T: DataType, X: Iterator<Item = T>>(mat: X, num_rows: usize, num_cols: usize) -> Vec<f64>
+{
+	let mut mat: Vec<f64> = mat.map(|v| v.get()).take(num_rows * num_cols).collect();
+	while mat.len() < num_rows * num_cols
+	{
+		mat.push(f64::NAN);
+	}
+	mat
+}
+
+fn default_dimensions(num_rows: usize, num_cols: usize) -> (f64, f64, f64, f64)
+{
+	(0.0, 0.0, (num_cols as f64 - 1.0).max(0.0), (num_rows as f64 - 1.0).max(0.0))
+}
+
+/// Computes the contours (lines of equal value) of a rectangular array of data at automatically determined levels, without plotting them.
+/// This uses the same conventions as `Axes3D::show_contours`.
+///
+/// # Arguments
+/// * `mat` - Row-major 2D array signifying the value of the datapoints
+/// * `num_rows` - Number of rows in the data array
+/// * `num_cols` - Number of columns in the data array
+/// * `dimensions` - Optional X and Y coordinates of the first and last data points (with the rest of the coordinates spaced evenly between).
+///                  By default this will be `(0, 0)` and `(num_rows - 1, num_cols - 1)`.
+/// * `style` - Style of the contours
+/// * `levels` - Auto picks some default number of levels, otherwise you can pass a set nominal number instead. The number is nominal as
+///              contours are placed at nice values, and thus there may be fewer of them than this number.
+pub fn contour_lines<T: DataType, X: Iterator<Item = T>>(mat: X, num_rows: usize, num_cols: usize, dimensions: Option<(f64, f64, f64, f64)>,
+                                                         style: ContourStyle, levels: AutoOption<u32>) -> Vec<ContourLine>
+{
+	let mat = collect_matrix(mat, num_rows, num_cols);
+	let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+	for &v in mat.iter().filter(|v| v.is_finite())
+	{
+		lo = lo.min(v);
+		hi = hi.max(v);
+	}
+	// Contours at the extremes of the data would only touch isolated points
+	let levels: Vec<f64> = nice_ticks(lo, hi, match levels
+	{
+		Fix(n) => n,
+		Auto => 5,
+	}).into_iter().filter(|&l| l > lo && l < hi).collect();
+	let dims = dimensions.unwrap_or(default_dimensions(num_rows, num_cols));
+	apply_style(compute_contour_lines(&mat[..], num_rows, num_cols, dims, &levels[..]), style)
+}
+
+/// Like `contour_lines`, but computes the contours at specific levels.
+///
+/// # Arguments
+/// * `mat` - Row-major 2D array signifying the value of the datapoints
+/// * `num_rows` - Number of rows in the data array
+/// * `num_cols` - Number of columns in the data array
+/// * `dimensions` - Optional X and Y coordinates of the first and last data points (with the rest of the coordinates spaced evenly between).
+///                  By default this will be `(0, 0)` and `(num_rows - 1, num_cols - 1)`.
+/// * `style` - Style of the contours
+/// * `levels` - Iterator for a set of levels.
+pub fn contour_lines_custom<T: DataType, X: Iterator<Item = T>,
+                            Tl: DataType, L: Iterator<Item = Tl>>(mat: X, num_rows: usize, num_cols: usize, dimensions: Option<(f64, f64, f64, f64)>,
+                                                                  style: ContourStyle, levels: L) -> Vec<ContourLine>
+{
+	let mat = collect_matrix(mat, num_rows, num_cols);
+	let levels: Vec<f64> = levels.map(|l| l.get()).collect();
+	let dims = dimensions.unwrap_or(default_dimensions(num_rows, num_cols));
+	apply_style(compute_contour_lines(&mat[..], num_rows, num_cols, dims, &levels[..]), style)
+}
+
+/// Computes the contour lines of a row-major 2D array at the given levels.
+/// `dimensions` are the X and Y coordinates of the first and last datapoints.
+pub fn compute_contour_lines(mat: &[f64], num_rows: usize, num_cols: usize, dimensions: (f64, f64, f64, f64), levels: &[f64]) -> Vec<ContourLine>
+{
+	assert!(mat.len() >= num_rows * num_cols, "Not enough data for a {}x{} array", num_rows, num_cols);
+	let grid = Grid
+	{
+		mat,
+		num_rows,
+		num_cols,
+		dimensions,
+	};
+	levels.iter().flat_map(|&l| trace_level(&grid, l)).collect()
+}
+
+#[cfg(test)]
+mod test
+{
+	use super::*;
+
+	fn peak() -> Vec<f64>
+	{
+		vec![0.0, 0.0, 0.0,
+		     0.0, 1.0, 0.0,
+		     0.0, 0.0, 0.0]
+	}
+
+	#[test]
+	fn peak_contour_is_closed_diamond()
+	{
+		let lines = compute_contour_lines(&peak()[..], 3, 3, default_dimensions(3, 3), &[0.5]);
+		assert_eq!(lines.len(), 1);
+		let points = &lines[0].points;
+		assert_eq!(points.len(), 5);
+		assert!(points[0] == points[4]);
+		for &(x, y) in points.iter()
+		{
+			assert!(((x - 1.0).abs() + (y - 1.0).abs() - 0.5).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn level_at_extremes_has_no_lines()
+	{
+		assert_eq!(compute_contour_lines(&peak()[..], 3, 3, default_dimensions(3, 3), &[1.0]).len(), 0);
+		assert_eq!(compute_contour_lines(&peak()[..], 3, 3, default_dimensions(3, 3), &[0.0]).len(), 0);
+	}
+
+	#[test]
+	fn auto_levels_exclude_extremes()
+	{
+		let lines = contour_lines(peak().into_iter(), 3, 3, None, Linear, Auto);
+		assert!(lines.len() > 0);
+		for line in lines.iter()
+		{
+			assert!(line.level > 0.0 && line.level < 1.0);
+			assert!(line.points.len() > 2);
+		}
+	}
+
+	#[test]
+	fn open_line_through_grid()
+	{
+		let mat = [0.0, 1.0,
+		           0.0, 1.0];
+		let lines = compute_contour_lines(&mat[..], 2, 2, default_dimensions(2, 2), &[0.25]);
+		assert_eq!(lines.len(), 1);
+		let mut points = lines[0].points.clone();
+		points.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+		assert_eq!(points, vec![(0.25, 0.0), (0.25, 1.0)]);
+	}
+
+	#[test]
+	fn band_polygons_match_contours()
+	{
+		let polygons = compute_band_polygons(&peak()[..], 3, 3, default_dimensions(3, 3), 0.5, 2.0);
+		assert_eq!(polygons.len(), 4);
+		for poly in polygons.iter()
+		{
+			// Each cell contributes a triangle between the peak and the diamond shaped contour at 0.5
+			assert_eq!(poly.len(), 3);
+			assert!(poly.iter().any(|&p| p == (1.0, 1.0)));
+			for &(x, y) in poly.iter().filter(|&&p| p != (1.0, 1.0))
+			{
+				assert!(((x - 1.0).abs() + (y - 1.0).abs() - 0.5).abs() < 1e-12);
+			}
+		}
+		assert_eq!(compute_band_polygons(&peak()[..], 3, 3, default_dimensions(3, 3), 2.0, 3.0).len(), 0);
+	}
+
+	#[test]
+	fn saddle_bands_follow_center()
+	{
+		// The center averages to 0.5, which is above the level, so the high corners are connected
+		let mat = [1.0, 0.0,
+		           0.0, 1.0];
+		assert_eq!(compute_band_polygons(&mat[..], 2, 2, default_dimensions(2, 2), 0.25, 1.0).len(), 1);
+		assert_eq!(compute_band_polygons(&mat[..], 2, 2, default_dimensions(2, 2), 0.0, 0.25).len(), 2);
+		assert_eq!(compute_contour_lines(&mat[..], 2, 2, default_dimensions(2, 2), &[0.25]).len(), 2);
+	}
+
+	#[test]
+	fn midpoint_by_length()
+	{
+		let line = ContourLine { level: 0.0, points: vec![(0.0, 0.0), (1.0, 0.0)] };
+		assert!(line.midpoint() == (0.5, 0.0));
+		// The short first segment doesn't pull the midpoint towards the start
+		let line = ContourLine { level: 0.0, points: vec![(0.0, 0.0), (0.1, 0.0), (0.2, 0.0), (4.0, 0.0)] };
+		let mid = line.midpoint();
+		assert!((mid.0 - 2.0).abs() < 1e-12 && mid.1 == 0.0);
+		// On a closed loop the midpoint is opposite the seam
+		let lines = compute_contour_lines(&peak()[..], 3, 3, default_dimensions(3, 3), &[0.5]);
+		let (seam, mid) = (lines[0].points[0], lines[0].midpoint());
+		assert!((mid.0 - (2.0 - seam.0)).abs() < 1e-12 && (mid.1 - (2.0 - seam.1)).abs() < 1e-12);
+	}
+
+	#[test]
+	fn open_b_spline_keeps_end_points()
+	{
+		let points = [(0.0, 0.0), (1.0, 2.0), (2.0, -1.0), (3.0, 0.5)];
+		let out = b_spline(&points[..], 5, 3);
+		assert!(out[0] == points[0]);
+		assert!(out[out.len() - 1] == points[3]);
+	}
+
+	#[test]
+	fn closed_b_spline_stays_closed()
+	{
+		let points = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)];
+		let out = b_spline(&points[..], 5, 3);
+		assert!(out[0] == out[out.len() - 1]);
+		// A periodic spline of a square is symmetric, so it doesn't pass through the corner it started at
+		assert!(out[0] != points[0]);
+		for &(x, y) in out.iter()
+		{
+			assert!(x >= 0.0 && x <= 1.0 && y >= 0.0 && y <= 1.0);
+		}
+	}
+
+	#[test]
+	fn cubic_spline_passes_through_points()
+	{
+		let points = [(0.0, 0.0), (1.0, 2.0), (2.0, -1.0), (3.0, 0.5)];
+		let out = cubic_spline(&points[..], 4);
+		assert_eq!(out.len(), 13);
+		for (i, &p) in points.iter().enumerate()
+		{
+			let q = out[i * 4];
+			assert!((p.0 - q.0).abs() < 1e-12 && (p.1 - q.1).abs() < 1e-12);
+		}
+	}
+}
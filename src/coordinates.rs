@@ -6,7 +6,9 @@ use std::fmt;
 
 pub use self::Coordinate::*;
 
-/// Specifies how to interpret the coordinate passed to a plotting command
+/// Specifies how to interpret the coordinate passed to a plotting command. Each component of a position is specified separately,
+/// so the coordinate systems can be mixed, e.g. `Axis(3.0)` for the X coordinate and `Graph(1.0)` for the Y coordinate refers to the
+/// top of the graph directly above `x = 3`.
 #[derive(Copy, Clone)]
 pub enum Coordinate
 {
@@ -14,7 +16,15 @@ pub enum Coordinate
 	/// You'd use this to place labels and other objects so that they remain in the same place relative to the graph no matter what you have plotted.
 	Graph(f64),
 	/// Coordinates match those on the axes. You'd use this to place labels and other objects relative to regions of interest in the graph (e.g. labeling the peak of a function)
-	Axis(f64)
+	Axis(f64),
+	/// Like `Axis`, but uses the secondary axes (the top X axis and the right Y axis, see `Axes2D::set_x2_range` and others)
+	Second(f64),
+	/// Coordinates are done relative to the whole figure (i.e. the canvas). (0, 0) is the bottom left corner and (1, 1) is the top right corner.
+	/// You'd use this to place objects relative to the figure when it contains multiple axes.
+	Screen(f64),
+	/// Coordinates are measured in the width and height of a character of the default font, from the bottom left corner of the figure.
+	/// You'd use this to place objects at a fixed distance from the edge of the figure, regardless of its size.
+	Character(f64),
 }
 
 impl fmt::Display for Coordinate
@@ -25,6 +35,9 @@ impl fmt::Display for Coordinate
 		{
 			Graph(x) => (" graph ", x),
 			Axis(x) => (" first ", x),
+			Second(x) => (" second ", x),
+			Screen(x) => (" screen ", x),
+			Character(x) => (" character ", x),
 		};
 		write!(buf, "{}{:.16e}", name, x)
 	}
@@ -28,13 +28,18 @@ fg.show();
 ~~~
 */
 
+#[cfg(feature = "image")]
+extern crate image;
+
 pub use coordinates::*;
 pub use datatype::*;
 pub use figure::*;
 pub use axes2d::Axes2D;
 pub use axes3d::Axes3D;
 pub use options::*;
-pub use axes_common::AxesCommon;
+pub use axes_common::{AxesCommon, TickAxis, XTickAxis, YTickAxis, X2TickAxis, Y2TickAxis, ZTickAxis, CBTickAxis, RTickAxis, TTickAxis};
+pub use contour::{ContourLine, contour_lines, contour_lines_custom};
+pub use query::{FitResult, Stats, StatsXY};
 
 #[macro_use]
 mod util;
@@ -47,3 +52,7 @@ mod figure;
 mod options; 
 mod datatype;
 mod coordinates;
+mod sampling;
+mod contour;
+mod query;
+mod ticks;
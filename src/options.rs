@@ -4,6 +4,7 @@
 
 pub use self::PlotOption::*;
 pub use self::FillRegionType::*;
+pub use self::EllipseUnitsType::*;
 pub use self::AlignType::*;
 pub use self::DashType::*;
 pub use self::ArrowheadType::*;
@@ -11,10 +12,21 @@ pub use self::AutoOption::*;
 pub use self::LabelOption::*;
 pub use self::TickOption::*;
 pub use self::Tick::*;
+pub use self::TickFormat::*;
+pub use self::RangeOption::*;
+pub use self::OffsetType::*;
 pub use self::BorderLocation2D::*;
 pub use self::LegendOption::*;
 pub use self::ContourStyle::*;
 pub use self::PaletteType::*;
+pub use self::BarMode::*;
+pub use self::PieOption::*;
+pub use self::ThetaOrigin::*;
+pub use self::SmoothType::*;
+pub use self::XAxis::*;
+pub use self::YAxis::*;
+pub use self::FillPatternType::*;
+pub use self::LayerType::*;
 
 /// An enumeration of plot options you can supply to plotting commands, governing
 /// things like line width, color and others
@@ -47,6 +59,9 @@ pub enum PlotOption<'l>
 	/// Sets the color of the plot element. The passed string can be a color name
 	/// (e.g. "black" works), or an HTML color specifier (e.g. "#FFFFFF" is white). This specifies the fill color of a filled plot.
 	Color(&'l str),
+	/// Sets the color of the plot element to the color of this value on the palette (i.e. of this position along the color bar).
+	/// Ignored if `Color` is also given.
+	PaletteColor(f64),
 	/// Sets the color of the border of a filled plot (if it has one). The passed string can be a color name
 	/// (e.g. "black" works), or an HTML color specifier (e.g. "#FFFFFF" is white).
 	BorderColor(&'l str),
@@ -60,6 +75,73 @@ pub enum PlotOption<'l>
 	ArrowType(ArrowheadType),
 	/// Sets the size of the arrowhead. This is specified in the units of graph (i.e. `1.0` would make the arrow as big as the graph).
 	ArrowSize(f64),
+	/// Smooths or otherwise transforms the data before plotting it. See SmoothType for the available methods.
+	Smooth(SmoothType),
+	/// Fills the shape with a hatch pattern instead of a solid color. The `Color` option sets the color of the pattern.
+	FillPattern(FillPatternType),
+	/// Sets whether a shape is drawn in front of or behind the plot elements. See LayerType for the available layers.
+	Layer(LayerType),
+	/// Sets which pair of axes the plot element is plotted against. By default, elements use the bottom X axis and the left Y axis.
+	Axes(XAxis, YAxis),
+	/// Sets which axes the diameters of ellipses are measured in. See EllipseUnitsType for the available units.
+	EllipseUnits(EllipseUnitsType),
+}
+
+/// Which axes the diameters of ellipses are measured in
+#[derive(Copy, Clone)]
+pub enum EllipseUnitsType
+{
+	/// The major diameter is in units of the X axis and the minor diameter in units of the Y axis (default)
+	UnitsXY,
+	/// Both diameters are in units of the X axis, so the ellipses are not distorted by the axis ranges
+	UnitsXX,
+	/// Both diameters are in units of the Y axis, so the ellipses are not distorted by the axis ranges
+	UnitsYY,
+}
+
+/// Which X axis a plot element is plotted against
+#[derive(Copy, Clone)]
+pub enum XAxis
+{
+	/// The bottom X axis
+	X1,
+	/// The top X axis
+	X2,
+}
+
+/// Which Y axis a plot element is plotted against
+#[derive(Copy, Clone)]
+pub enum YAxis
+{
+	/// The left Y axis
+	Y1,
+	/// The right Y axis
+	Y2,
+}
+
+/// An enumeration of the ways data can be smoothed before being plotted. All of these sort the data by the x value first
+#[derive(Copy, Clone)]
+pub enum SmoothType
+{
+	/// Draws a natural cubic spline through the data points
+	CSplines,
+	/// Draws a natural cubic spline approximating the data points (i.e. not necessarily passing through them)
+	ACSplines,
+	/// Draws a Bezier curve using all the data points as control points
+	Bezier,
+	/// Like `Bezier`, but the data is first made monotonic in x (as with `Unique`), so the curve is a function of x
+	SBezier,
+	/// Replaces the points with the same x value with a single point with the average y value
+	Unique,
+	/// Replaces the points with the same x value with a single point with the total y value (e.g. to make histograms)
+	Frequency,
+	/// Like `Frequency`, but uses the running total, producing a cumulative distribution
+	Cumulative,
+	/// Like `Cumulative`, but normalized so that the last value is 1
+	CNormal,
+	/// Draws a kernel density estimate of the distribution of the x values, with the y values acting as weights of each point.
+	/// The argument is the bandwidth of the Gaussian kernel. Auto picks an optimal bandwidth assuming the data is normally distributed.
+	KDensity(AutoOption<f64>),
 }
 
 /// An enumeration of possible fill regions
@@ -71,6 +153,52 @@ pub enum FillRegionType
 	Between
 }
 
+/// An enumeration of possible fill patterns
+#[derive(Copy, Clone)]
+pub enum FillPatternType
+{
+	/// Diagonal lines in both directions
+	CrossHatch,
+	/// Like `CrossHatch`, but with the lines closer together
+	DenseCrossHatch,
+	/// Diagonal lines going up to the right
+	ForwardDiagonals,
+	/// Diagonal lines going down to the right
+	BackwardDiagonals,
+	/// Like `ForwardDiagonals`, but with the lines closer together
+	DenseForwardDiagonals,
+	/// Like `BackwardDiagonals`, but with the lines closer together
+	DenseBackwardDiagonals,
+}
+
+impl FillPatternType
+{
+	pub fn to_int(&self) -> i32
+	{
+		match *self
+		{
+			CrossHatch => 1,
+			DenseCrossHatch => 2,
+			ForwardDiagonals => 4,
+			BackwardDiagonals => 5,
+			DenseForwardDiagonals => 6,
+			DenseBackwardDiagonals => 7,
+		}
+	}
+}
+
+/// An enumeration of layers that shapes can be drawn on
+#[derive(Copy, Clone)]
+pub enum LayerType
+{
+	/// In front of the plot elements
+	Front,
+	/// Behind the plot elements, but in front of the grid (default)
+	Back,
+	/// Behind everything, including the grid
+	Behind,
+}
+
 /// An enumeration of possible text and label alignments
 #[derive(Copy, Clone)]
 pub enum AlignType
@@ -203,6 +331,45 @@ pub enum TickOption
 	MajorScale(f64)
 }
 
+/// Options that control the range of an axis, see `AxesCommon::set_range_options`
+#[derive(Copy, Clone)]
+pub enum RangeOption
+{
+	/// Reverses the direction of the axis, e.g. so that depth grows downward
+	ReverseRange,
+	/// When the minimum of the range is automatic, don't extend it to the next tick
+	FixMin,
+	/// When the maximum of the range is automatic, don't extend it to the next tick
+	FixMax,
+	/// Like `FixMin` and `FixMax` combined, so that the automatic range tightly fits the data
+	NoExtend,
+}
+
+/// Padding of an automatically determined range, see `Axes2D::set_offsets`
+#[derive(Copy, Clone)]
+pub enum OffsetType
+{
+	/// Pads by an amount in the units of the axis
+	AxisOffset(f64),
+	/// Pads by a fraction of the range of the axis, e.g. `GraphOffset(0.05)` for 5%
+	GraphOffset(f64),
+}
+
+/// Specifies how the tick labels of an axis are formatted. To format the labels with a Rust function, see `AxesCommon::set_x_tick_formatter` and its counterparts for the other axes
+#[derive(Copy, Clone)]
+pub enum TickFormat<'l>
+{
+	/// A gnuplot format string, which is like a C printf format string with some additional specifiers. For example, `"%.2f"` gives
+	/// two decimal places, `"%.0f s"` appends a unit and `"%P{/Symbol p}"` labels multiples of pi
+	Printf(&'l str),
+	/// Scientific notation, e.g. `1.5×10^3`. The argument is the number of decimal places of the mantissa
+	Scientific(u32),
+	/// Engineering notation with an SI prefix, e.g. `1.5k` or `20µ`. The argument is the number of decimal places of the mantissa
+	SIPrefix(u32),
+	/// Appends a percent sign, e.g. `50%`. The values are expected to already be percentages. The argument is the number of decimal places
+	Percent(u32),
+}
+
 /// Specifies a type of axis tick
 pub enum Tick<T>
 {
@@ -276,6 +443,48 @@ pub enum PaletteType
 	CubeHelix(f32, f32, f32, f32),
 }
 
+/// Specifies how the series of a bar chart are laid out
+#[derive(Copy, Clone)]
+pub enum BarMode
+{
+	/// The bars of each series are placed side by side within each category
+	Grouped,
+	/// The bars of each series are stacked on top of each other within each category
+	Stacked,
+	/// Like `Stacked`, but the values are normalized so that each category sums to 100
+	PercentStacked,
+}
+
+/// Pie chart options
+#[derive(Copy, Clone)]
+pub enum PieOption
+{
+	/// Labels each slice with its percentage of the total
+	PercentLabels,
+	/// Moves a slice (specified by its index) away from the center by a certain fraction of the pie radius.
+	/// Can be specified multiple times to explode several slices.
+	Explode(usize, f64),
+	/// Cuts a hole of a certain radius (as a fraction of the pie radius) in the center of the pie, turning it into a donut chart.
+	/// The hole is painted in the background color of the terminal
+	HoleRadius(f64),
+	/// Angle (in degrees, counter-clockwise from the positive X axis) at which the first slice starts. The default is 90 (i.e. the top of the pie).
+	StartAngle(f64),
+}
+
+/// Where the zero angle of polar axes is located, see `Axes2D::set_polar`
+#[derive(Copy, Clone)]
+pub enum ThetaOrigin
+{
+	/// The zero angle points to the right (default gnuplot behaviour)
+	ThetaRight,
+	/// The zero angle points up
+	ThetaTop,
+	/// The zero angle points to the left
+	ThetaLeft,
+	/// The zero angle points down
+	ThetaBottom,
+}
+
 /// A gray palette
 pub const GRAY: PaletteType = Gray(1.0);
 /// Default Gnuplot palette
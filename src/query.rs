@@ -0,0 +1,450 @@
+// Copyright (c) 2013-2014 by SiegeLord
+//
+// All rights reserved. Distributed under LGPL 3.0. For full terms see the file LICENSE.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use datatype::*;
+use options::*;
+use writer::Writer;
+
+/// Runs a script through a fresh gnuplot process and collects the lines it printed. The script is expected to
+/// `set print "-"` and then print lines of the form `NAME value...`, which are returned keyed by `NAME`.
+pub fn run_gnuplot(script: &[u8]) -> Result<HashMap<String, Vec<f64>>, String>
+{
+	let mut child = Command::new("gnuplot")
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.map_err(|e| format!("Couldn't spawn gnuplot: {}", e))?;
+
+	{
+		let stdin = child.stdin.as_mut().expect("No stdin!?");
+		stdin.write_all(script).map_err(|e| format!("Couldn't write to gnuplot: {}", e))?;
+	}
+
+	let output = child.wait_with_output().map_err(|e| format!("Couldn't read gnuplot output: {}", e))?;
+	if !output.status.success()
+	{
+		return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+	}
+
+	let mut ret = HashMap::new();
+	for line in String::from_utf8_lossy(&output.stdout).lines()
+	{
+		let mut words = line.split_whitespace();
+		if let Some(name) = words.next()
+		{
+			let values = words.map(|w| w.parse().unwrap_or(f64::NAN)).collect();
+			ret.insert(name.to_string(), values);
+		}
+	}
+	Ok(ret)
+}
+
+/// Looks up the first value printed for `name`
+pub fn get_value(values: &HashMap<String, Vec<f64>>, name: &str) -> Result<f64, String>
+{
+	values.get(name).and_then(|v| v.first().cloned()).ok_or(format!("gnuplot did not report {}", name))
+}
+
+/// The result of fitting a model to data, see `Axes2D::fit`
+#[derive(Clone, Debug)]
+pub struct FitResult
+{
+	/// The model expression that was fitted
+	pub model: String,
+	/// Names and fitted values of the parameters, in the order they were passed in
+	pub params: Vec<(String, f64)>,
+	/// Asymptotic standard errors of the parameters, in the same order as `params`
+	pub errors: Vec<f64>,
+	/// Sum of squared residuals
+	pub chi_square: f64,
+	/// Number of degrees of freedom (number of data points minus number of parameters)
+	pub ndf: u32,
+	/// Correlation matrix of the parameters, in the same order as `params`
+	pub correlation: Vec<Vec<f64>>,
+}
+
+impl FitResult
+{
+	/// Returns the fitted value of the named parameter
+	pub fn get(&self, name: &str) -> Option<f64>
+	{
+		self.params.iter().find(|&(n, _)| n == name).map(|&(_, v)| v)
+	}
+
+	/// Sum of squared residuals divided by the number of degrees of freedom
+	pub fn reduced_chi_square(&self) -> f64
+	{
+		self.chi_square / self.ndf as f64
+	}
+
+	/// Returns a gnuplot expression that evaluates the model with the fitted parameters, suitable for passing to `Axes2D::expression`
+	pub fn expression(&self) -> String
+	{
+		let mut ret = "(".to_string();
+		for (name, value) in self.params.iter()
+		{
+			ret.push_str(&format!("{} = {:.17e}, ", name, value));
+		}
+		ret.push_str(&self.model);
+		ret.push(')');
+		ret
+	}
+}
+
+/// Checks that `name` can be used as a gnuplot variable name
+fn check_identifier(name: &str) -> Result<(), String>
+{
+	let mut chars = name.chars();
+	let valid = match chars.next()
+	{
+		Some(c) => (c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+		None => false
+	};
+	if !valid || name == "x"
+	{
+		return Err(format!("Invalid parameter name: {:?}", name));
+	}
+	Ok(())
+}
+
+/// Checks that `expr` is a single gnuplot expression, i.e. that it can't end the command it is placed in
+fn check_expression(expr: &str) -> Result<(), String>
+{
+	if expr.trim().is_empty() || expr.chars().any(|c| c.is_control() || c == ';' || c == '#')
+	{
+		return Err(format!("Invalid expression: {:?}", expr));
+	}
+	Ok(())
+}
+
+/// Creates a script that fits `model` to the data. The parameter names and the model are validated, as they are pasted into the script as is.
+pub fn fit_script(x: &[f64], y: &[f64], model: &str, initial_params: &[(&str, f64)]) -> Result<Vec<u8>, String>
+{
+	check_expression(model)?;
+	for &(name, _) in initial_params.iter()
+	{
+		check_identifier(name)?;
+	}
+
+	let mut c = vec![];
+	writeln!(&mut c, "set print \"-\"");
+	writeln!(&mut c, "set fit quiet nolog errorvariables covariancevariables");
+	for &(name, value) in initial_params.iter()
+	{
+		writeln!(&mut c, "{} = {:.17e}", name, value);
+	}
+	let names: Vec<&str> = initial_params.iter().map(|&(n, _)| n).collect();
+	writeln!(&mut c, "fit {} '-' using 1:2 via {}", model, names.join(","));
+	for (&x, &y) in x.iter().zip(y.iter())
+	{
+		writeln!(&mut c, "{:.17e} {:.17e}", x, y);
+	}
+	writeln!(&mut c, "e");
+	for (i, name) in names.iter().enumerate()
+	{
+		writeln!(&mut c, "print sprintf(\"{0} %.17e %.17e\", {0}, {0}_err)", name);
+		for other in names[i..].iter()
+		{
+			writeln!(&mut c, "print sprintf(\"FIT_COV_{0}_{1} %.17e\", FIT_COV_{0}_{1})", name, other);
+		}
+	}
+	writeln!(&mut c, "print sprintf(\"FIT_WSSR %.17e\", FIT_WSSR)");
+	writeln!(&mut c, "print sprintf(\"FIT_NDF %d\", FIT_NDF)");
+	Ok(c)
+}
+
+pub fn parse_fit(values: &HashMap<String, Vec<f64>>, model: &str, initial_params: &[(&str, f64)]) -> Result<FitResult, String>
+{
+	let n = initial_params.len();
+	let mut params = Vec::with_capacity(n);
+	let mut errors = Vec::with_capacity(n);
+	for &(name, _) in initial_params.iter()
+	{
+		let v = values.get(name).ok_or(format!("gnuplot did not report {}", name))?;
+		if v.len() < 2
+		{
+			return Err(format!("gnuplot did not report {}", name));
+		}
+		params.push((name.to_string(), v[0]));
+		errors.push(v[1]);
+	}
+
+	let mut cov = vec![vec![0.0; n]; n];
+	for i in 0..n
+	{
+		for j in i..n
+		{
+			let c = get_value(values, &format!("FIT_COV_{}_{}", initial_params[i].0, initial_params[j].0))?;
+			cov[i][j] = c;
+			cov[j][i] = c;
+		}
+	}
+	let correlation = (0..n).map(|i|
+	{
+		(0..n).map(|j|
+		{
+			let norm = (cov[i][i] * cov[j][j]).sqrt();
+			if i == j
+			{
+				1.0
+			}
+			else if norm > 0.0
+			{
+				cov[i][j] / norm
+			}
+			else
+			{
+				0.0
+			}
+		}).collect()
+	}).collect();
+
+	Ok(FitResult
+	{
+		model: model.to_string(),
+		params: params,
+		errors: errors,
+		chi_square: get_value(values, "FIT_WSSR")?,
+		ndf: get_value(values, "FIT_NDF")? as u32,
+		correlation: correlation,
+	})
+}
+
+/// Summary statistics of a single column of data, see `stats`
+#[derive(Copy, Clone, Debug)]
+pub struct Stats
+{
+	/// Number of data points used
+	pub records: u32,
+	/// Number of data points that were skipped because they were outside of the range passed to `stats_xy`
+	pub out_of_range: u32,
+	/// Smallest value
+	pub min: f64,
+	/// Largest value
+	pub max: f64,
+	/// Arithmetic mean
+	pub mean: f64,
+	/// Standard deviation, treating the data as the whole population
+	pub stddev: f64,
+	/// Standard deviation, treating the data as a sample
+	pub sample_stddev: f64,
+	/// First quartile (25th percentile)
+	pub lo_quartile: f64,
+	/// Median (50th percentile)
+	pub median: f64,
+	/// Third quartile (75th percentile)
+	pub up_quartile: f64,
+	/// Sum of the values
+	pub sum: f64,
+	/// Sum of squares
+	pub sum_sq: f64,
+	/// Skewness of the distribution of the values
+	pub skewness: f64,
+	/// Kurtosis of the distribution of the values
+	pub kurtosis: f64,
+}
+
+/// Summary statistics of two columns of data, see `stats_xy`
+#[derive(Copy, Clone, Debug)]
+pub struct StatsXY
+{
+	/// Statistics of the x values
+	pub x: Stats,
+	/// Statistics of the y values
+	pub y: Stats,
+	/// Correlation coefficient between x and y
+	pub correlation: f64,
+	/// Slope of the linear regression of y on x
+	pub slope: f64,
+	/// Intercept of the linear regression of y on x
+	pub intercept: f64,
+	/// The x value where y is smallest
+	pub pos_min_y: f64,
+	/// The x value where y is largest
+	pub pos_max_y: f64,
+}
+
+static STATS_VARS: [&str; 12] = ["min", "max", "mean", "stddev", "ssd", "lo_quartile", "median", "up_quartile", "sum", "sumsq", "skewness", "kurtosis"];
+
+fn write_range(c: &mut Vec<u8>, range: (AutoOption<f64>, AutoOption<f64>))
+{
+	c.write_str(" [");
+	match range.0
+	{
+		Fix(v) => { write!(c, "{:.12e}", v); },
+		Auto => { c.write_str("*"); }
+	}
+	c.write_str(":");
+	match range.1
+	{
+		Fix(v) => { write!(c, "{:.12e}", v); },
+		Auto => { c.write_str("*"); }
+	}
+	c.write_str("]");
+}
+
+/// Creates a script that runs `stats` on one or two columns of data. The ranges restrict which points are used, like they would when plotting.
+pub fn stats_script(columns: &[&[f64]], ranges: &[(AutoOption<f64>, AutoOption<f64>)]) -> Vec<u8>
+{
+	let mut c = vec![];
+	writeln!(&mut c, "set print \"-\"");
+	c.write_str("stats");
+	for &range in ranges.iter()
+	{
+		write_range(&mut c, range);
+	}
+	let using: Vec<String> = (1..columns.len() + 1).map(|i| i.to_string()).collect();
+	writeln!(&mut c, " '-' using {} prefix \"STATS\" nooutput", using.join(":"));
+	for i in 0..columns[0].len()
+	{
+		for col in columns.iter()
+		{
+			write!(&mut c, "{:.17e} ", col[i]);
+		}
+		c.write_str("\n");
+	}
+	writeln!(&mut c, "e");
+	writeln!(&mut c, "print sprintf(\"STATS_records %d\", STATS_records)");
+	writeln!(&mut c, "print sprintf(\"STATS_outofrange %d\", STATS_outofrange)");
+
+	let suffixes: &[&str] = if columns.len() == 1 { &[""] } else { &["_x", "_y"] };
+	for var in STATS_VARS.iter()
+	{
+		for suffix in suffixes.iter()
+		{
+			writeln!(&mut c, "print sprintf(\"STATS_{0}{1} %.17e\", STATS_{0}{1})", var, suffix);
+		}
+	}
+	if columns.len() > 1
+	{
+		for var in ["correlation", "slope", "intercept", "pos_min_y", "pos_max_y"].iter()
+		{
+			writeln!(&mut c, "print sprintf(\"STATS_{0} %.17e\", STATS_{0})", var);
+		}
+	}
+	c
+}
+
+/// Extracts the statistics of one column, `suffix` is `""` for single-column statistics, and `"_x"` or `"_y"` otherwise
+pub fn parse_stats(values: &HashMap<String, Vec<f64>>, suffix: &str) -> Result<Stats, String>
+{
+	let get = |name: &str| get_value(values, &format!("STATS_{}{}", name, suffix));
+	Ok(Stats
+	{
+		records: get_value(values, "STATS_records")? as u32,
+		out_of_range: get_value(values, "STATS_outofrange")? as u32,
+		min: get("min")?,
+		max: get("max")?,
+		mean: get("mean")?,
+		stddev: get("stddev")?,
+		sample_stddev: get("ssd")?,
+		lo_quartile: get("lo_quartile")?,
+		median: get("median")?,
+		up_quartile: get("up_quartile")?,
+		sum: get("sum")?,
+		sum_sq: get("sumsq")?,
+		skewness: get("skewness")?,
+		kurtosis: get("kurtosis")?,
+	})
+}
+
+pub fn parse_stats_xy(values: &HashMap<String, Vec<f64>>) -> Result<StatsXY, String>
+{
+	Ok(StatsXY
+	{
+		x: parse_stats(values, "_x")?,
+		y: parse_stats(values, "_y")?,
+		correlation: get_value(values, "STATS_correlation")?,
+		slope: get_value(values, "STATS_slope")?,
+		intercept: get_value(values, "STATS_intercept")?,
+		pos_min_y: get_value(values, "STATS_pos_min_y")?,
+		pos_max_y: get_value(values, "STATS_pos_max_y")?,
+	})
+}
+
+/// Computes summary statistics of a dataset using gnuplot's `stats` command, which runs a separate gnuplot process and waits for it to finish.
+/// Requires gnuplot 5.0 or later. Values that are not finite are skipped. If gnuplot fails, the error message it reported is returned instead.
+/// # Arguments
+/// * `data` - Iterator for the values
+pub fn stats<T: DataType, D: Iterator<Item = T>>(data: D) -> Result<Stats, String>
+{
+	let data: Vec<f64> = data.map(|v| v.get()).filter(|v| v.is_finite()).collect();
+	if data.is_empty()
+	{
+		return Err("No data".to_string());
+	}
+	let values = run_gnuplot(&stats_script(&[&data], &[]))?;
+	parse_stats(&values, "")
+}
+
+/// Computes summary statistics of paired data using gnuplot's `stats` command, which runs a separate gnuplot process and waits for it to finish.
+/// Requires gnuplot 5.0 or later. Points where either value is not finite are skipped. If gnuplot fails, the error message it reported is returned instead.
+/// # Arguments
+/// * `x` - Iterator for the x values
+/// * `y` - Iterator for the y values
+/// * `x_range` - Points with x values outside of this range are skipped (e.g. pass the range of the X axis to only consider the points that are shown)
+/// * `y_range` - Points with y values outside of this range are skipped
+pub fn stats_xy<Tx: DataType, X: Iterator<Item = Tx>, Ty: DataType, Y: Iterator<Item = Ty>>(x: X, y: Y, x_range: (AutoOption<f64>, AutoOption<f64>),
+                                                                                        y_range: (AutoOption<f64>, AutoOption<f64>)) -> Result<StatsXY, String>
+{
+	let (xs, ys): (Vec<f64>, Vec<f64>) = x.zip(y).map(|(x, y)| (x.get(), y.get())).filter(|&(x, y)| x.is_finite() && y.is_finite()).unzip();
+	if xs.is_empty()
+	{
+		return Err("No data".to_string());
+	}
+	let values = run_gnuplot(&stats_script(&[&xs, &ys], &[x_range, y_range]))?;
+	parse_stats_xy(&values)
+}
+
+#[cfg(test)]
+mod test
+{
+	use super::*;
+
+	fn values(entries: &[(&str, &[f64])]) -> HashMap<String, Vec<f64>>
+	{
+		entries.iter().map(|&(n, v)| (n.to_string(), v.to_vec())).collect()
+	}
+
+	#[test]
+	fn parse_fit_result()
+	{
+		let values = values(&[("a", &[2.0, 0.1]), ("b", &[-1.0, 0.5]),
+		                      ("FIT_COV_a_a", &[0.01]), ("FIT_COV_a_b", &[-0.025]), ("FIT_COV_b_b", &[0.25]),
+		                      ("FIT_WSSR", &[3.0]), ("FIT_NDF", &[6.0])]);
+		let res = parse_fit(&values, "a * x + b", &[("a", 1.0), ("b", 0.0)]).unwrap();
+		assert_eq!(res.params, vec![("a".to_string(), 2.0), ("b".to_string(), -1.0)]);
+		assert_eq!(res.errors, vec![0.1, 0.5]);
+		assert_eq!(res.get("b"), Some(-1.0));
+		assert_eq!(res.get("c"), None);
+		assert_eq!(res.ndf, 6);
+		assert_eq!(res.reduced_chi_square(), 0.5);
+		assert!((res.correlation[0][1] + 0.5).abs() < 1e-12);
+		assert!((res.correlation[1][0] + 0.5).abs() < 1e-12);
+		assert_eq!(res.correlation[0][0], 1.0);
+	}
+
+	#[test]
+	fn parse_fit_missing_value()
+	{
+		let values = values(&[("a", &[2.0, 0.1]), ("FIT_WSSR", &[3.0]), ("FIT_NDF", &[6.0])]);
+		assert!(parse_fit(&values, "a * x", &[("a", 1.0)]).is_err());
+	}
+
+	#[test]
+	fn fit_script_validation()
+	{
+		assert!(fit_script(&[], &[], "a * x", &[("a", 1.0)]).is_ok());
+		assert!(fit_script(&[], &[], "a * x", &[("x", 1.0)]).is_err());
+		assert!(fit_script(&[], &[], "a * x", &[("a b", 1.0)]).is_err());
+		assert!(fit_script(&[], &[], "a * x", &[("1a", 1.0)]).is_err());
+		assert!(fit_script(&[], &[], "a * x\nsystem(\"ls\")", &[("a", 1.0)]).is_err());
+		assert!(fit_script(&[], &[], "a * x; a = 1", &[("a", 1.0)]).is_err());
+	}
+}
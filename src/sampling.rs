@@ -0,0 +1,190 @@
+// Copyright (c) 2013-2014 by SiegeLord
+//
+// All rights reserved. Distributed under LGPL 3.0. For full terms see the file LICENSE.
+
+use std::f64;
+
+/// Maximum number of times a single initial interval is bisected
+const MAX_DEPTH: u32 = 10;
+/// Cosine of the maximum angle (10 degrees) allowed between successive segments before they are refined
+const MIN_COS: f64 = 0.984807753;
+/// Length (relative to the extent of the curve) above which a segment that is still bent at the maximum depth is treated as a discontinuity
+const MAX_JUMP: f64 = 0.05;
+
+fn is_finite(p: &[f64; 3]) -> bool
+{
+	p.iter().all(|v| v.is_finite())
+}
+
+struct Sampler<F>
+{
+	f: F,
+	scale: [f64; 3],
+	points: Vec<[f64; 3]>,
+}
+
+impl<F: FnMut(f64) -> [f64; 3]> Sampler<F>
+{
+	fn length(&self, a: &[f64; 3], b: &[f64; 3]) -> f64
+	{
+		let mut l = 0.0;
+		for i in 0..3
+		{
+			let d = (b[i] - a[i]) / self.scale[i];
+			l += d * d;
+		}
+		l.sqrt()
+	}
+
+	fn is_smooth(&self, a: &[f64; 3], m: &[f64; 3], b: &[f64; 3]) -> bool
+	{
+		let mut d1 = [0.0; 3];
+		let mut d2 = [0.0; 3];
+		for i in 0..3
+		{
+			d1[i] = (m[i] - a[i]) / self.scale[i];
+			d2[i] = (b[i] - m[i]) / self.scale[i];
+		}
+		let dot = d1[0] * d2[0] + d1[1] * d2[1] + d1[2] * d2[2];
+		let l1 = (d1[0] * d1[0] + d1[1] * d1[1] + d1[2] * d1[2]).sqrt();
+		let l2 = (d2[0] * d2[0] + d2[1] * d2[1] + d2[2] * d2[2]).sqrt();
+		if l1 * l2 < 1e-12
+		{
+			return true;
+		}
+		dot / (l1 * l2) > MIN_COS
+	}
+
+	/// Adds the points between `a` (exclusive) and `b` (inclusive) to the output
+	fn refine(&mut self, ta: f64, a: [f64; 3], tb: f64, b: [f64; 3], depth: u32)
+	{
+		if depth < MAX_DEPTH
+		{
+			let tm = 0.5 * (ta + tb);
+			let m = (self.f)(tm);
+			let refine = match (is_finite(&a), is_finite(&m), is_finite(&b))
+			{
+				(true, true, true) => !self.is_smooth(&a, &m, &b),
+				(false, false, false) => false,
+				_ => true
+			};
+
+			if refine
+			{
+				self.refine(ta, a, tm, m, depth + 1);
+				self.refine(tm, m, tb, b, depth + 1);
+				return;
+			}
+		}
+		else if is_finite(&a) && is_finite(&b) && self.length(&a, &b) > MAX_JUMP
+		{
+			// Still not resolved after all the refinement, so break the line here
+			self.points.push([f64::NAN; 3]);
+		}
+		self.points.push(b);
+	}
+}
+
+/// Samples a curve parameterized by `t`, starting with `samples` evenly spaced values of `t` between `t_min` and `t_max`
+/// and then bisecting the intervals where the curve bends sharply. Points where the curve is not finite are returned as NaN,
+/// which causes gnuplot to break the line there. A NaN point is also inserted at jump discontinuities.
+pub fn sample_curve<F: FnMut(f64) -> [f64; 3]>(t_min: f64, t_max: f64, samples: usize, f: F) -> Vec<[f64; 3]>
+{
+	let samples = if samples < 2 { 2 } else { samples };
+	let mut sampler = Sampler
+	{
+		f,
+		scale: [1.0; 3],
+		points: vec![],
+	};
+
+	let ts: Vec<f64> = (0..samples).map(|i| t_min + (t_max - t_min) * i as f64 / (samples - 1) as f64).collect();
+	let ps: Vec<[f64; 3]> = ts.iter().map(|&t| (sampler.f)(t)).collect();
+
+	// Bends are measured relative to the extent of the curve, so that the units of each coordinate don't matter
+	for i in 0..3
+	{
+		let mut lo = f64::INFINITY;
+		let mut hi = f64::NEG_INFINITY;
+		for p in ps.iter().filter(|p| is_finite(p))
+		{
+			lo = lo.min(p[i]);
+			hi = hi.max(p[i]);
+		}
+		if hi > lo
+		{
+			sampler.scale[i] = hi - lo;
+		}
+	}
+
+	sampler.points.push(ps[0]);
+	for i in 1..samples
+	{
+		sampler.refine(ts[i - 1], ps[i - 1], ts[i], ps[i], 0);
+	}
+
+	for p in sampler.points.iter_mut()
+	{
+		if !is_finite(p)
+		{
+			*p = [f64::NAN; 3];
+		}
+	}
+	sampler.points
+}
+
+#[cfg(test)]
+mod test
+{
+	use super::*;
+
+	fn step(t: f64) -> [f64; 3]
+	{
+		[t, if t < 0.3 { 0.0 } else { 1.0 }, 0.0]
+	}
+
+	#[test]
+	fn straight_line_is_not_refined()
+	{
+		let points = sample_curve(0.0, 1.0, 5, |t| [t, 2.0 * t, 0.0]);
+		assert_eq!(points.len(), 5);
+		assert!(points.iter().zip([0.0, 0.25, 0.5, 0.75, 1.0].iter()).all(|(p, &t)| p[0] == t && p[1] == 2.0 * t));
+	}
+
+	#[test]
+	fn jump_is_broken_with_nan()
+	{
+		let points = sample_curve(0.0, 1.0, 11, step);
+		let gaps: Vec<usize> = (0..points.len()).filter(|&i| points[i][0].is_nan()).collect();
+		assert_eq!(gaps.len(), 1);
+		let (before, after) = (points[gaps[0] - 1], points[gaps[0] + 1]);
+		assert!(before[0] < 0.3 && before[1] == 0.0);
+		assert!(after[0] >= 0.3 && after[1] == 1.0);
+	}
+
+	#[test]
+	fn refinement_stops_at_max_depth()
+	{
+		let points = sample_curve(0.0, 1.0, 2, step);
+		let gap = (0..points.len()).find(|&i| points[i][0].is_nan()).unwrap();
+		// The interval containing the jump is bisected MAX_DEPTH times and no further
+		let width = points[gap + 1][0] - points[gap - 1][0];
+		assert_eq!(width, 1.0 / (1 << MAX_DEPTH) as f64);
+		assert!(points.len() <= 2 * MAX_DEPTH as usize + 3);
+	}
+
+	#[test]
+	fn non_finite_points_become_nan()
+	{
+		let points = sample_curve(-1.0, 1.0, 3, |t| [t, 1.0 / t, t.sqrt()]);
+		assert!(points.iter().any(|p| p.iter().all(|v| v.is_nan())));
+		for p in points.iter()
+		{
+			assert!(is_finite(p) || p.iter().all(|v| v.is_nan()));
+		}
+		let last = points[points.len() - 1];
+		assert!(last == [1.0, 1.0, 1.0]);
+		// sqrt is not defined for negative t
+		assert!(points.iter().filter(|p| is_finite(p)).all(|p| p[0] > 0.0));
+	}
+}
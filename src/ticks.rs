@@ -0,0 +1,99 @@
+// Copyright (c) 2013-2014 by SiegeLord
+//
+// All rights reserved. Distributed under LGPL 3.0. For full terms see the file LICENSE.
+
+/// Picks about `n` evenly spaced values at round numbers between `lo` and `hi`
+pub fn nice_ticks(lo: f64, hi: f64, n: u32) -> Vec<f64>
+{
+	if !lo.is_finite() || !hi.is_finite() || hi <= lo || n == 0
+	{
+		return vec![];
+	}
+	let raw_step = (hi - lo) / n as f64;
+	let exponent = raw_step.log10().floor() as i32;
+	let magnitude = 10.0f64.powi(exponent);
+	let mantissa = [1.0, 2.0, 2.5, 5.0, 10.0].iter().cloned().find(|&m| m * magnitude >= raw_step).unwrap_or(10.0);
+	let step = mantissa * magnitude;
+
+	// Dividing by an exact power of 10 avoids values like 0.30000000000000004
+	let to_value = |k: f64| if exponent < 0
+	{
+		k * mantissa / 10.0f64.powi(-exponent)
+	}
+	else
+	{
+		k * mantissa * magnitude
+	};
+
+	let mut ticks = vec![];
+	// The tolerance keeps the end points when the step is inexact, e.g. 3 * 0.1 > 0.3
+	let eps = step * 1e-9;
+	let mut k = ((lo - eps) / step).ceil();
+	while k * step <= hi + eps
+	{
+		ticks.push(to_value(k));
+		k += 1.0;
+	}
+	ticks
+}
+
+/// Picks tick positions for an axis spanning `lo` to `hi`. Logarithmic axes get ticks at the powers of the base, if there are at least two of them.
+pub fn auto_ticks(lo: f64, hi: f64, log_base: Option<f64>) -> Vec<f64>
+{
+	let (lo, hi) = if lo > hi { (hi, lo) } else { (lo, hi) };
+	match log_base
+	{
+		Some(base) if lo > 0.0 =>
+		{
+			let first = (lo.ln() / base.ln() - 1e-9).ceil() as i32;
+			let last = (hi.ln() / base.ln() + 1e-9).floor() as i32;
+			if last > first
+			{
+				return (first..last + 1).map(|e| base.powi(e)).collect();
+			}
+		},
+		_ => ()
+	}
+	nice_ticks(lo, hi, 5)
+}
+
+#[cfg(test)]
+mod test
+{
+	use super::*;
+
+	#[test]
+	fn nice_ticks_round_values()
+	{
+		assert_eq!(nice_ticks(0.0, 1.0, 5), vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+		assert_eq!(nice_ticks(0.0, 0.3, 3), vec![0.0, 0.1, 0.2, 0.3]);
+		assert_eq!(nice_ticks(-7.0, 23.0, 3), vec![0.0, 10.0, 20.0]);
+		assert_eq!(nice_ticks(0.0, 10.0, 4), vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+	}
+
+	#[test]
+	fn nice_ticks_degenerate()
+	{
+		assert!(nice_ticks(1.0, 1.0, 5).is_empty());
+		assert!(nice_ticks(2.0, 1.0, 5).is_empty());
+		assert!(nice_ticks(0.0, 1.0, 0).is_empty());
+		assert!(nice_ticks(0.0, ::std::f64::NAN, 5).is_empty());
+		assert!(nice_ticks(0.0, ::std::f64::INFINITY, 5).is_empty());
+	}
+
+	#[test]
+	fn auto_ticks_linear()
+	{
+		assert_eq!(auto_ticks(1.0, 0.0, None), nice_ticks(0.0, 1.0, 5));
+	}
+
+	#[test]
+	fn auto_ticks_log()
+	{
+		assert_eq!(auto_ticks(0.5, 1000.0, Some(10.0)), vec![1.0, 10.0, 100.0, 1000.0]);
+		// A single power of the base in range falls back to linear ticks
+		assert_eq!(auto_ticks(2.0, 20.0, Some(10.0)), nice_ticks(2.0, 20.0, 5));
+		// Non-positive ranges can't be logarithmic
+		assert_eq!(auto_ticks(-1.0, 1.0, Some(10.0)), nice_ticks(-1.0, 1.0, 5));
+	}
+}